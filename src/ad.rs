@@ -0,0 +1,586 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{AdvertisementReport, BtUuid, DeviceInfo};
+
+/// Company identifier of Apple, Inc.
+pub const APPLE_COMPANY_ID: u16 = 0x004c;
+/// Company identifier of Microsoft.
+pub const MICROSOFT_COMPANY_ID: u16 = 0x0006;
+/// Scenario type of Connected Devices Platform beacons, the only one defined.
+pub const CDP_SCENARIO_BLUETOOTH: u8 = 0x01;
+/// Company identifier of Ruuvi Innovations Ltd.
+pub const RUUVI_COMPANY_ID: u16 = 0x0499;
+/// Service UUID Eddystone frames are advertised under.
+pub const EDDYSTONE_SERVICE_UUID: BtUuid = BtUuid::from_u16(0xfeaa);
+
+/// Advertising data decoded into a known format.
+#[derive(Debug, Clone)]
+pub enum AdPayload {
+    IBeacon(IBeacon),
+    AltBeacon(AltBeacon),
+    Eddystone(Eddystone),
+    MicrosoftCdp(MicrosoftCdp),
+    /// Apple Continuity messages other than iBeacon.
+    AppleContinuity(Vec<ContinuityMessage>),
+    Ruuvi(Ruuvi),
+    /// Manufacturer data decoded by a decoder added to an [`AdParser`].
+    Custom(CustomPayload),
+}
+
+/// Apple iBeacon, advertised as manufacturer data of [`APPLE_COMPANY_ID`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IBeacon {
+    /// Proximity UUID, e.g. `f7826da6-4fa2-4e98-8024-bc5b71e0893e`.
+    pub uuid: BtUuid,
+    pub major: u16,
+    pub minor: u16,
+    /// Calibrated RSSI at 1 m.
+    pub measured_power: i8,
+}
+
+impl IBeacon {
+    /// Parses the manufacturer data of [`APPLE_COMPANY_ID`].
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() != 23 || data[..2] != [0x02, 0x15] {
+            return None;
+        }
+        Some(Self {
+            uuid: BtUuid::from_u128(u128::from_be_bytes(bytes(data, 2)?)),
+            major: be_u16(data, 18)?,
+            minor: be_u16(data, 20)?,
+            measured_power: data[22] as i8,
+        })
+    }
+}
+
+/// AltBeacon, advertised as manufacturer data of any company.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AltBeacon {
+    /// Company identifier the beacon was advertised with.
+    pub company_id: u16,
+    /// Beacon identifier; usually a 16-byte UUID followed by two 2-byte values.
+    pub beacon_id: [u8; 20],
+    /// Calibrated RSSI at 1 m.
+    pub reference_rssi: i8,
+    /// Reserved for use by the manufacturer.
+    pub manufacturer_reserved: u8,
+}
+
+impl AltBeacon {
+    /// Parses the manufacturer data of `company_id`.
+    pub fn parse(company_id: u16, data: &[u8]) -> Option<Self> {
+        if data.len() != 24 || data[..2] != [0xbe, 0xac] {
+            return None;
+        }
+        Some(Self {
+            company_id,
+            beacon_id: bytes(data, 2)?,
+            reference_rssi: data[22] as i8,
+            manufacturer_reserved: data[23],
+        })
+    }
+}
+
+/// A Google Eddystone frame, advertised as service data of [`EDDYSTONE_SERVICE_UUID`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Eddystone {
+    Uid(EddystoneUid),
+    Url(EddystoneUrl),
+    Tlm(EddystoneTlm),
+    Eid(EddystoneEid),
+}
+
+impl Eddystone {
+    /// Parses the service data of [`EDDYSTONE_SERVICE_UUID`].
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        match *data.first()? {
+            0x00 => EddystoneUid::parse(data).map(Eddystone::Uid),
+            0x10 => EddystoneUrl::parse(data).map(Eddystone::Url),
+            0x20 => EddystoneTlm::parse(data).map(Eddystone::Tlm),
+            0x30 => EddystoneEid::parse(data).map(Eddystone::Eid),
+            _ => None,
+        }
+    }
+}
+
+/// Eddystone-UID frame identifying a beacon by namespace and instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EddystoneUid {
+    /// Calibrated transmit power at 0 m.
+    pub tx_power: i8,
+    pub namespace: [u8; 10],
+    pub instance: [u8; 6],
+}
+
+impl EddystoneUid {
+    /// Parses a UID frame, including its frame type byte.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 18 || data[0] != 0x00 {
+            return None;
+        }
+        Some(Self {
+            tx_power: data[1] as i8,
+            namespace: bytes(data, 2)?,
+            instance: bytes(data, 12)?,
+        })
+    }
+}
+
+/// Eddystone-URL frame carrying a compressed URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EddystoneUrl {
+    /// Calibrated transmit power at 0 m.
+    pub tx_power: i8,
+    /// The expanded URL.
+    pub url: String,
+}
+
+impl EddystoneUrl {
+    /// Parses a URL frame, including its frame type byte.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 3 || data[0] != 0x10 {
+            return None;
+        }
+
+        let mut url = String::from(match data[2] {
+            0x00 => "http://www.",
+            0x01 => "https://www.",
+            0x02 => "http://",
+            0x03 => "https://",
+            _ => return None,
+        });
+        for &byte in &data[3..] {
+            match byte {
+                0x00 => url.push_str(".com/"),
+                0x01 => url.push_str(".org/"),
+                0x02 => url.push_str(".edu/"),
+                0x03 => url.push_str(".net/"),
+                0x04 => url.push_str(".info/"),
+                0x05 => url.push_str(".biz/"),
+                0x06 => url.push_str(".gov/"),
+                0x07 => url.push_str(".com"),
+                0x08 => url.push_str(".org"),
+                0x09 => url.push_str(".edu"),
+                0x0a => url.push_str(".net"),
+                0x0b => url.push_str(".info"),
+                0x0c => url.push_str(".biz"),
+                0x0d => url.push_str(".gov"),
+                0x21..=0x7e => url.push(byte as char),
+                _ => return None,
+            }
+        }
+
+        Some(Self {
+            tx_power: data[1] as i8,
+            url,
+        })
+    }
+}
+
+/// Eddystone-TLM frame carrying beacon telemetry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EddystoneTlm {
+    /// Unencrypted telemetry (version 0).
+    Plain {
+        /// Battery voltage in mV, `None` if not supported.
+        battery: Option<u16>,
+        /// Beacon temperature in °C, `None` if not supported.
+        temperature: Option<f64>,
+        /// Number of frames advertised since power-up.
+        advertisement_count: u32,
+        /// Time since power-up.
+        uptime: Duration,
+    },
+    /// Encrypted telemetry (version 1), paired with an EID frame.
+    Encrypted {
+        data: [u8; 12],
+        salt: u16,
+        integrity_check: u16,
+    },
+}
+
+impl EddystoneTlm {
+    /// Parses a TLM frame, including its frame type byte.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 14 || data[0] != 0x20 {
+            return None;
+        }
+        match data[1] {
+            0x00 => {
+                let battery = be_u16(data, 2)?;
+                let temperature = be_u16(data, 4)?;
+                Some(EddystoneTlm::Plain {
+                    battery: (battery != 0).then_some(battery),
+                    temperature: (temperature != 0x8000).then(|| temperature as i16 as f64 / 256.0),
+                    advertisement_count: be_u32(data, 6)?,
+                    uptime: Duration::from_millis(be_u32(data, 10)? as u64 * 100),
+                })
+            }
+            0x01 if data.len() >= 18 => Some(EddystoneTlm::Encrypted {
+                data: bytes(data, 2)?,
+                salt: be_u16(data, 14)?,
+                integrity_check: be_u16(data, 16)?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Eddystone-EID frame carrying a rotating ephemeral identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EddystoneEid {
+    /// Calibrated transmit power at 0 m.
+    pub tx_power: i8,
+    pub ephemeral_id: [u8; 8],
+}
+
+impl EddystoneEid {
+    /// Parses an EID frame, including its frame type byte.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 10 || data[0] != 0x30 {
+            return None;
+        }
+        Some(Self {
+            tx_power: data[1] as i8,
+            ephemeral_id: bytes(data, 2)?,
+        })
+    }
+}
+
+/// Microsoft Connected Devices Platform beacon, advertised as manufacturer
+/// data of [`MICROSOFT_COMPANY_ID`] by Windows, Xbox and Android devices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MicrosoftCdp {
+    /// Scenario type, always [`CDP_SCENARIO_BLUETOOTH`].
+    pub scenario_type: u8,
+    /// Beacon version.
+    pub version: u8,
+    /// Device type, e.g. 1 for Xbox One, 9 for a Windows 10 desktop or 8 for Android.
+    pub device_type: u8,
+    /// Version and flags byte.
+    pub flags: u8,
+    pub salt: [u8; 4],
+    /// Hash identifying the device.
+    pub device_hash: Vec<u8>,
+}
+
+impl MicrosoftCdp {
+    /// Parses the manufacturer data of [`MICROSOFT_COMPANY_ID`].
+    ///
+    /// # Returns
+    /// `None` if the data is truncated or of another scenario type than
+    /// [`CDP_SCENARIO_BLUETOOTH`].
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 || data[0] != CDP_SCENARIO_BLUETOOTH {
+            return None;
+        }
+        Some(Self {
+            scenario_type: data[0],
+            version: data[1] >> 5,
+            device_type: data[1] & 0x1f,
+            flags: data[2],
+            salt: bytes(data, 4)?,
+            device_hash: data[8..].to_vec(),
+        })
+    }
+}
+
+/// Type of an Apple Continuity message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContinuityType {
+    IBeacon,
+    AirDrop,
+    ProximityPairing,
+    AirPlayTarget,
+    AirPlaySource,
+    MagicSwitch,
+    Handoff,
+    TetheringTarget,
+    TetheringSource,
+    NearbyAction,
+    NearbyInfo,
+    FindMy,
+    Unknown(u8),
+}
+
+impl From<u8> for ContinuityType {
+    fn from(value: u8) -> Self {
+        match value {
+            0x02 => ContinuityType::IBeacon,
+            0x05 => ContinuityType::AirDrop,
+            0x07 => ContinuityType::ProximityPairing,
+            0x09 => ContinuityType::AirPlayTarget,
+            0x0a => ContinuityType::AirPlaySource,
+            0x0b => ContinuityType::MagicSwitch,
+            0x0c => ContinuityType::Handoff,
+            0x0d => ContinuityType::TetheringTarget,
+            0x0e => ContinuityType::TetheringSource,
+            0x0f => ContinuityType::NearbyAction,
+            0x10 => ContinuityType::NearbyInfo,
+            0x12 => ContinuityType::FindMy,
+            other => ContinuityType::Unknown(other),
+        }
+    }
+}
+
+/// One type-length-value message of Apple's Continuity protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContinuityMessage {
+    pub kind: ContinuityType,
+    /// The message body; its layout depends on the type and is undocumented.
+    pub data: Vec<u8>,
+}
+
+impl ContinuityMessage {
+    /// Splits the manufacturer data of [`APPLE_COMPANY_ID`] into messages.
+    ///
+    /// # Returns
+    /// `None` if the data is empty or a message is truncated.
+    pub fn parse_all(data: &[u8]) -> Option<Vec<Self>> {
+        if data.is_empty() {
+            return None;
+        }
+        let mut messages = Vec::new();
+        let mut rest = data;
+        while !rest.is_empty() {
+            let kind = *rest.first()?;
+            let length = *rest.get(1)? as usize;
+            let body = rest.get(2..2 + length)?;
+            messages.push(Self {
+                kind: kind.into(),
+                data: body.to_vec(),
+            });
+            rest = &rest[2 + length..];
+        }
+        Some(messages)
+    }
+}
+
+/// Sensor readings of a RuuviTag, advertised as manufacturer data of
+/// [`RUUVI_COMPANY_ID`] in data format 3 (RAWv1) or 5 (RAWv2).
+///
+/// Readings a format lacks or marks as invalid are `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ruuvi {
+    /// Data format, 3 or 5.
+    pub format: u8,
+    /// Temperature in °C.
+    pub temperature: Option<f64>,
+    /// Relative humidity in %.
+    pub humidity: Option<f64>,
+    /// Atmospheric pressure in Pa.
+    pub pressure: Option<u32>,
+    /// Acceleration along the X, Y and Z axes in mG.
+    pub acceleration: Option<[i16; 3]>,
+    /// Battery voltage in mV.
+    pub battery: Option<u16>,
+    /// Transmit power in dBm.
+    pub tx_power: Option<i8>,
+    pub movement_counter: Option<u8>,
+    pub measurement_sequence: Option<u16>,
+    /// MAC address of the tag.
+    pub mac: Option<[u8; 6]>,
+}
+
+impl Ruuvi {
+    /// Parses the manufacturer data of [`RUUVI_COMPANY_ID`].
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        match *data.first()? {
+            3 => Self::parse_v3(data),
+            5 => Self::parse_v5(data),
+            _ => None,
+        }
+    }
+
+    /// Parses data format 3 (RAWv1).
+    fn parse_v3(data: &[u8]) -> Option<Self> {
+        if data.len() < 14 {
+            return None;
+        }
+        let magnitude = (data[2] & 0x7f) as f64 + data[3] as f64 / 100.0;
+        let temperature = if data[2] & 0x80 != 0 {
+            -magnitude
+        } else {
+            magnitude
+        };
+        Some(Self {
+            format: 3,
+            temperature: Some(temperature),
+            humidity: Some(data[1] as f64 / 2.0),
+            pressure: Some(be_u16(data, 4)? as u32 + 50_000),
+            acceleration: Some([be_i16(data, 6)?, be_i16(data, 8)?, be_i16(data, 10)?]),
+            battery: Some(be_u16(data, 12)?),
+            tx_power: None,
+            movement_counter: None,
+            measurement_sequence: None,
+            mac: None,
+        })
+    }
+
+    /// Parses data format 5 (RAWv2).
+    fn parse_v5(data: &[u8]) -> Option<Self> {
+        if data.len() < 24 {
+            return None;
+        }
+        let temperature = be_i16(data, 1)?;
+        let humidity = be_u16(data, 3)?;
+        let pressure = be_u16(data, 5)?;
+        let acceleration = [be_i16(data, 7)?, be_i16(data, 9)?, be_i16(data, 11)?];
+        let power = be_u16(data, 13)?;
+        let battery = power >> 5;
+        let tx_power = power & 0x1f;
+        let sequence = be_u16(data, 16)?;
+        let mac: [u8; 6] = bytes(data, 18)?;
+
+        Some(Self {
+            format: 5,
+            temperature: (temperature != i16::MIN).then_some(temperature as f64 * 0.005),
+            humidity: (humidity != u16::MAX).then_some(humidity as f64 * 0.0025),
+            pressure: (pressure != u16::MAX).then(|| pressure as u32 + 50_000),
+            acceleration: (!acceleration.contains(&i16::MIN)).then_some(acceleration),
+            battery: (battery != 0x7ff).then(|| battery + 1600),
+            tx_power: (tx_power != 0x1f).then(|| tx_power as i8 * 2 - 40),
+            movement_counter: (data[15] != u8::MAX).then_some(data[15]),
+            measurement_sequence: (sequence != u16::MAX).then_some(sequence),
+            mac: (mac != [0xff; 6]).then_some(mac),
+        })
+    }
+}
+
+/// Manufacturer data decoded by a custom decoder.
+#[derive(Clone)]
+pub struct CustomPayload {
+    /// Company identifier the data was advertised with.
+    pub company_id: u16,
+    /// The value returned by the decoder.
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+impl CustomPayload {
+    /// Returns the decoded value if it is a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+}
+
+impl std::fmt::Debug for CustomPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomPayload")
+            .field("company_id", &self.company_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Decoder turning manufacturer data into a custom payload.
+type Decoder = Box<dyn Fn(&[u8]) -> Option<Arc<dyn Any + Send + Sync>> + Send + Sync>;
+
+/// Decodes manufacturer and service data using the built-in formats and
+/// decoders registered for specific company identifiers.
+///
+/// Registered decoders take precedence over the built-in formats.
+#[derive(Default)]
+pub struct AdParser {
+    /// Custom decoders keyed by company identifier.
+    decoders: HashMap<u16, Decoder>,
+}
+
+impl AdParser {
+    /// Creates a parser knowing only the built-in formats.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a decoder for the manufacturer data of `company_id`.
+    ///
+    /// The decoded value is reported as [`AdPayload::Custom`] and can be
+    /// retrieved with [`CustomPayload::downcast_ref`].
+    ///
+    /// # Arguments
+    /// * `company_id` - The Bluetooth SIG company identifier.
+    /// * `decoder` - Parses the manufacturer data, returning `None` if it is not understood.
+    pub fn register<T, D>(&mut self, company_id: u16, decoder: D)
+    where
+        T: Any + Send + Sync,
+        D: Fn(&[u8]) -> Option<T> + Send + Sync + 'static,
+    {
+        self.decoders.insert(
+            company_id,
+            Box::new(move |data| decoder(data).map(|value| Arc::new(value) as _)),
+        );
+    }
+
+    /// Decodes the manufacturer data advertised with `company_id`.
+    pub fn parse_manufacturer_data(&self, company_id: u16, data: &[u8]) -> Option<AdPayload> {
+        if let Some(decoder) = self.decoders.get(&company_id) {
+            if let Some(value) = decoder(data) {
+                return Some(AdPayload::Custom(CustomPayload { company_id, value }));
+            }
+        }
+
+        match company_id {
+            APPLE_COMPANY_ID => IBeacon::parse(data)
+                .map(AdPayload::IBeacon)
+                .or_else(|| ContinuityMessage::parse_all(data).map(AdPayload::AppleContinuity)),
+            MICROSOFT_COMPANY_ID => MicrosoftCdp::parse(data).map(AdPayload::MicrosoftCdp),
+            RUUVI_COMPANY_ID => Ruuvi::parse(data).map(AdPayload::Ruuvi),
+            _ => None,
+        }
+        .or_else(|| AltBeacon::parse(company_id, data).map(AdPayload::AltBeacon))
+    }
+
+    /// Decodes the service data advertised for the service `uuid`.
+    pub fn parse_service_data(&self, uuid: &BtUuid, data: &[u8]) -> Option<AdPayload> {
+        if *uuid == EDDYSTONE_SERVICE_UUID {
+            return Eddystone::parse(data).map(AdPayload::Eddystone);
+        }
+        None
+    }
+
+    /// Decodes every manufacturer and service data entry of a device.
+    pub fn parse_device(&self, device: &DeviceInfo) -> Vec<AdPayload> {
+        self.parse_all(&device.manufacturer_data, &device.service_data)
+    }
+
+    /// Decodes every manufacturer and service data entry of an advertisement.
+    pub fn parse_report(&self, report: &AdvertisementReport) -> Vec<AdPayload> {
+        self.parse_all(&report.manufacturer_data, &report.service_data)
+    }
+
+    /// Decodes manufacturer data followed by service data.
+    fn parse_all(
+        &self,
+        manufacturer_data: &HashMap<u16, Vec<u8>>,
+        service_data: &HashMap<BtUuid, Vec<u8>>,
+    ) -> Vec<AdPayload> {
+        let manufacturer = manufacturer_data
+            .iter()
+            .filter_map(|(company_id, data)| self.parse_manufacturer_data(*company_id, data));
+        let service = service_data
+            .iter()
+            .filter_map(|(uuid, data)| self.parse_service_data(uuid, data));
+        manufacturer.chain(service).collect()
+    }
+}
+
+/// Copies `N` bytes starting at `offset`.
+fn bytes<const N: usize>(data: &[u8], offset: usize) -> Option<[u8; N]> {
+    data.get(offset..offset + N)?.try_into().ok()
+}
+
+/// Reads a big-endian `u16` at `offset`.
+fn be_u16(data: &[u8], offset: usize) -> Option<u16> {
+    bytes(data, offset).map(u16::from_be_bytes)
+}
+
+/// Reads a big-endian `i16` at `offset`.
+fn be_i16(data: &[u8], offset: usize) -> Option<i16> {
+    bytes(data, offset).map(i16::from_be_bytes)
+}
+
+/// Reads a big-endian `u32` at `offset`.
+fn be_u32(data: &[u8], offset: usize) -> Option<u32> {
+    bytes(data, offset).map(u32::from_be_bytes)
+}
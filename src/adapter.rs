@@ -1,57 +1,451 @@
+use std::collections::HashMap;
+
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, SerializeDict, Str, Type, Value};
+
+use crate::cache::replace;
+
+/// D-Bus interface implemented by Bluetooth adapters.
+pub const ADAPTER_INTERFACE: &str = "org.bluez.Adapter1";
+
+/// Transport used for discovery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[zvariant(signature = "s")]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    /// Interleaved BR/EDR and LE scanning, depending on what the adapter supports.
+    Auto,
+    /// BR/EDR inquiry only.
+    BrEdr,
+    /// LE scanning only.
+    Le,
+}
+
+/// Filter applied by the controller while discovering devices.
+///
+/// Unset fields are not sent to BlueZ and keep their default behaviour.
+#[derive(Debug, Clone, Default, SerializeDict, Type)]
+#[zvariant(signature = "a{sv}", rename_all = "PascalCase")]
+pub struct DiscoveryFilter {
+    /// Only report devices advertising any of these service UUIDs.
+    #[zvariant(rename = "UUIDs")]
+    pub uuids: Option<Vec<crate::BtUuid>>,
+    /// Only report devices received with at least this RSSI, in dBm.
+    #[zvariant(rename = "RSSI")]
+    pub rssi: Option<i16>,
+    /// Only report devices whose path loss is at most this value, in dB.
+    pub pathloss: Option<u16>,
+    /// Transport to scan on.
+    pub transport: Option<Transport>,
+    /// Whether to report every advertisement instead of only data changes.
+    pub duplicate_data: Option<bool>,
+    /// Whether to only report devices in discoverable mode.
+    pub discoverable: Option<bool>,
+    /// Only report devices whose address or name starts with this prefix.
+    pub pattern: Option<String>,
+}
+
 /// Defines the `Adapter` trait for interfacing with Bluetooth adapters via D-Bus.
 /// Provides methods to control and retrieve information about the adapter.
 #[zbus::proxy(default_service = "org.bluez", interface = "org.bluez.Adapter1")]
 pub trait Adapter {
     /// Starts Bluetooth device discovery.
-    fn start_discovery(&self) -> zbus::Result<()>;
+    fn start_discovery(&self) -> crate::Result<()>;
 
     /// Stops Bluetooth device discovery.
-    fn stop_discovery(&self) -> zbus::Result<()>;
+    fn stop_discovery(&self) -> crate::Result<()>;
 
     /// Removes a previously paired Bluetooth device.
     ///
     /// # Arguments
     /// * `device` - The object path of the device to be removed.
-    fn remove_device(&self, device: zbus::zvariant::OwnedObjectPath) -> zbus::Result<()>;
+    fn remove_device(&self, device: zbus::zvariant::OwnedObjectPath) -> crate::Result<()>;
+
+    /// Sets the filter used by subsequent discovery sessions of this client.
+    ///
+    /// # Arguments
+    /// * `filter` - The filter to apply; a default filter clears it.
+    fn set_discovery_filter(&self, filter: &DiscoveryFilter) -> crate::Result<()>;
+
+    /// Lists the discovery filter keys supported by the adapter.
+    fn get_discovery_filters(&self) -> crate::Result<Vec<String>>;
+
+    /// Creates and connects a device without discovering it first.
+    ///
+    /// This method is experimental and requires bluetoothd to run with `--experimental`.
+    ///
+    /// # Arguments
+    /// * `properties` - `Address` and optionally `AddressType` (`"public"` or `"random"`).
+    ///
+    /// # Returns
+    /// The object path of the new device.
+    fn connect_device(
+        &self,
+        properties: HashMap<&str, Value<'_>>,
+    ) -> crate::Result<OwnedObjectPath>;
 
     /// Retrieves the Bluetooth adapter's unique address (MAC address).
     #[zbus(property)]
-    fn address(&self) -> zbus::Result<String>;
+    fn address(&self) -> crate::Result<String>;
 
     /// Retrieves the name of the Bluetooth adapter.
     #[zbus(property)]
-    fn name(&self) -> zbus::Result<String>;
+    fn name(&self) -> crate::Result<String>;
 
     /// Retrieves the alias name of the adapter, which can be customized.
     #[zbus(property)]
-    fn alias(&self) -> zbus::Result<String>;
+    fn alias(&self) -> crate::Result<String>;
 
     /// Sets a new alias name for the adapter.
     ///
     /// # Arguments
     /// * `alias` - The new alias to set.
     #[zbus(property)]
-    fn set_alias(&self, alias: &str) -> zbus::Result<()>;
+    fn set_alias(&self, alias: &str) -> crate::Result<()>;
 
     /// Checks if the adapter is powered on.
     #[zbus(property)]
-    fn powered(&self) -> zbus::Result<bool>;
+    fn powered(&self) -> crate::Result<bool>;
 
     /// Enables or disables the Bluetooth adapter.
     ///
     /// # Arguments
     /// * `powered` - `true` to power on, `false` to power off.
     #[zbus(property)]
-    fn set_powered(&self, powered: bool) -> zbus::Result<()>;
+    fn set_powered(&self, powered: bool) -> crate::Result<()>;
 
     /// Checks if the adapter is in discoverable mode.
     #[zbus(property)]
-    fn discoverable(&self) -> zbus::Result<bool>;
+    fn discoverable(&self) -> crate::Result<bool>;
 
     /// Sets the adapter's discoverable mode.
     ///
     /// # Arguments
     /// * `discoverable` - `true` to make discoverable, `false` to hide.
     #[zbus(property)]
-    fn set_discoverable(&self, discoverable: bool) -> zbus::Result<()>;
+    fn set_discoverable(&self, discoverable: bool) -> crate::Result<()>;
+
+    /// Retrieves the type of the adapter's address, `"public"` or `"random"`.
+    #[zbus(property)]
+    fn address_type(&self) -> crate::Result<String>;
+
+    /// Retrieves the Bluetooth class of device.
+    #[zbus(property)]
+    fn class(&self) -> crate::Result<crate::ClassOfDevice>;
+
+    /// Retrieves the time in seconds after which discoverable mode is turned off, 0 for never.
+    #[zbus(property)]
+    fn discoverable_timeout(&self) -> crate::Result<u32>;
+
+    /// Sets the discoverable timeout in seconds.
+    ///
+    /// # Arguments
+    /// * `timeout` - The timeout, 0 to stay discoverable.
+    #[zbus(property)]
+    fn set_discoverable_timeout(&self, timeout: u32) -> crate::Result<()>;
+
+    /// Checks if the adapter accepts pairing requests.
+    #[zbus(property)]
+    fn pairable(&self) -> crate::Result<bool>;
+
+    /// Enables or disables pairing.
+    ///
+    /// # Arguments
+    /// * `pairable` - `true` to accept pairing requests.
+    #[zbus(property)]
+    fn set_pairable(&self, pairable: bool) -> crate::Result<()>;
+
+    /// Retrieves the time in seconds after which pairable mode is turned off, 0 for never.
+    #[zbus(property)]
+    fn pairable_timeout(&self) -> crate::Result<u32>;
+
+    /// Sets the pairable timeout in seconds.
+    ///
+    /// # Arguments
+    /// * `timeout` - The timeout, 0 to stay pairable.
+    #[zbus(property)]
+    fn set_pairable_timeout(&self, timeout: u32) -> crate::Result<()>;
+
+    /// Checks if a discovery session is active.
+    #[zbus(property)]
+    fn discovering(&self) -> crate::Result<bool>;
+
+    /// Retrieves the UUIDs of the services available on the adapter.
+    #[zbus(property, name = "UUIDs")]
+    fn uuids(&self) -> crate::Result<Vec<crate::BtUuid>>;
+
+    /// Retrieves the remote device ID information in modalias format.
+    #[zbus(property)]
+    fn modalias(&self) -> crate::Result<String>;
+
+    /// Retrieves the supported roles, e.g. `"central"` and `"peripheral"`.
+    #[zbus(property)]
+    fn roles(&self) -> crate::Result<Vec<String>>;
+
+    /// Retrieves the UUIDs of the enabled experimental features.
+    #[zbus(property)]
+    fn experimental_features(&self) -> crate::Result<Vec<crate::BtUuid>>;
+
+    /// Retrieves the power state, e.g. `"on"`, `"off"`, `"off-blocked"` or a transition.
+    #[zbus(property)]
+    fn power_state(&self) -> crate::Result<String>;
+
+    /// Retrieves the company identifier of the controller manufacturer.
+    #[zbus(property)]
+    fn manufacturer(&self) -> crate::Result<u16>;
+
+    /// Retrieves the Bluetooth core specification version of the controller.
+    #[zbus(property)]
+    fn version(&self) -> crate::Result<u8>;
+}
+
+/// An [`AdapterInfo`] field, named after the `org.bluez.Adapter1` property backing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdapterField {
+    Address,
+    AddressType,
+    Name,
+    Alias,
+    Powered,
+    Discoverable,
+    Discovering,
+    Pairable,
+    PowerState,
+}
+
+impl AdapterField {
+    /// Returns the name of the D-Bus property backing the field.
+    pub fn property_name(&self) -> &'static str {
+        match self {
+            AdapterField::Address => "Address",
+            AdapterField::AddressType => "AddressType",
+            AdapterField::Name => "Name",
+            AdapterField::Alias => "Alias",
+            AdapterField::Powered => "Powered",
+            AdapterField::Discoverable => "Discoverable",
+            AdapterField::Discovering => "Discovering",
+            AdapterField::Pairable => "Pairable",
+            AdapterField::PowerState => "PowerState",
+        }
+    }
+
+    /// Returns the field backed by the D-Bus property `name`, if any.
+    pub fn from_property(name: &str) -> Option<Self> {
+        match name {
+            "Address" => Some(AdapterField::Address),
+            "AddressType" => Some(AdapterField::AddressType),
+            "Name" => Some(AdapterField::Name),
+            "Alias" => Some(AdapterField::Alias),
+            "Powered" => Some(AdapterField::Powered),
+            "Discoverable" => Some(AdapterField::Discoverable),
+            "Discovering" => Some(AdapterField::Discovering),
+            "Pairable" => Some(AdapterField::Pairable),
+            "PowerState" => Some(AdapterField::PowerState),
+            _ => None,
+        }
+    }
+}
+
+/// Snapshot of an `org.bluez.Adapter1` object.
+#[derive(Debug, Clone)]
+pub struct AdapterInfo {
+    /// Object path of the adapter, e.g. `/org/bluez/hci0`.
+    pub path: OwnedObjectPath,
+    pub address: String,
+    pub address_type: Option<String>,
+    pub name: Option<String>,
+    pub alias: Option<String>,
+    pub powered: bool,
+    pub discoverable: bool,
+    pub discovering: bool,
+    pub pairable: bool,
+    pub power_state: Option<String>,
+}
+
+impl AdapterInfo {
+    /// Builds an `AdapterInfo` from the `org.bluez.Adapter1` properties of an object.
+    ///
+    /// # Arguments
+    /// * `path` - The object path of the adapter.
+    /// * `properties` - The adapter's property map.
+    ///
+    /// # Returns
+    /// `None` if the mandatory `Address` property is missing.
+    pub fn from_properties(
+        path: OwnedObjectPath,
+        properties: &HashMap<String, OwnedValue>,
+    ) -> Option<Self> {
+        let string = |key: &str| {
+            properties
+                .get(key)
+                .and_then(|v| v.downcast_ref::<Str>().ok())
+                .map(|s| s.as_str().to_owned())
+        };
+        let flag = |key: &str| {
+            properties
+                .get(key)
+                .and_then(|v| v.downcast_ref::<bool>().ok())
+                .unwrap_or(false)
+        };
+
+        Some(Self {
+            address: string("Address")?,
+            address_type: string("AddressType"),
+            name: string("Name"),
+            alias: string("Alias"),
+            powered: flag("Powered"),
+            discoverable: flag("Discoverable"),
+            discovering: flag("Discovering"),
+            pairable: flag("Pairable"),
+            power_state: string("PowerState"),
+            path,
+        })
+    }
+
+    /// Applies the changed properties of a `PropertiesChanged` signal.
+    ///
+    /// # Returns
+    /// The fields whose value changed.
+    pub fn apply_changes(&mut self, changed: &HashMap<&str, Value<'_>>) -> Vec<AdapterField> {
+        changed
+            .iter()
+            .filter_map(|(name, value)| {
+                let field = AdapterField::from_property(name)?;
+                self.apply_field(field, Some(value)).then_some(field)
+            })
+            .collect()
+    }
+
+    /// Clears the invalidated properties of a `PropertiesChanged` signal.
+    ///
+    /// Optional fields are reset to `None` and flags to `false`. The address is
+    /// never cleared.
+    ///
+    /// # Returns
+    /// The fields whose value changed.
+    pub fn invalidate(&mut self, invalidated: &[&str]) -> Vec<AdapterField> {
+        invalidated
+            .iter()
+            .filter_map(|name| AdapterField::from_property(name))
+            .filter(|field| self.apply_field(*field, None))
+            .collect()
+    }
+
+    /// Sets `field` from `value`, or clears it if `value` is `None`, returning
+    /// whether it changed. Values of the wrong type are ignored.
+    fn apply_field(&mut self, field: AdapterField, value: Option<&Value<'_>>) -> bool {
+        let string = || match value {
+            Some(v) => v
+                .downcast_ref::<Str>()
+                .ok()
+                .map(|s| Some(s.as_str().to_owned())),
+            None => Some(None),
+        };
+        let flag = || match value {
+            Some(v) => v.downcast_ref::<bool>().ok(),
+            None => Some(false),
+        };
+
+        match field {
+            AdapterField::Address => match string() {
+                Some(Some(address)) => replace(&mut self.address, address),
+                _ => false,
+            },
+            AdapterField::AddressType => {
+                string().is_some_and(|v| replace(&mut self.address_type, v))
+            }
+            AdapterField::Name => string().is_some_and(|v| replace(&mut self.name, v)),
+            AdapterField::Alias => string().is_some_and(|v| replace(&mut self.alias, v)),
+            AdapterField::PowerState => string().is_some_and(|v| replace(&mut self.power_state, v)),
+            AdapterField::Powered => flag().is_some_and(|v| replace(&mut self.powered, v)),
+            AdapterField::Discoverable => {
+                flag().is_some_and(|v| replace(&mut self.discoverable, v))
+            }
+            AdapterField::Discovering => flag().is_some_and(|v| replace(&mut self.discovering, v)),
+            AdapterField::Pairable => flag().is_some_and(|v| replace(&mut self.pairable, v)),
+        }
+    }
+}
+
+/// An adapter whose properties changed, together with the fields that changed.
+#[derive(Debug, Clone)]
+pub struct AdapterChange {
+    /// The adapter after the change was applied.
+    pub adapter: AdapterInfo,
+    /// The fields that changed value.
+    pub changed: Vec<AdapterField>,
+}
+
+/// An adapter appearing on or disappearing from the bus, e.g. a USB dongle being
+/// plugged in, or changing state.
+///
+/// [`adapter_events`] only reports adapters being added and removed; the
+/// remaining variants are reported by [`crate::Monitor::adapter_events`].
+#[derive(Debug, Clone)]
+pub enum AdapterEvent {
+    /// An adapter BlueZ already knew about when monitoring started.
+    Present(AdapterInfo),
+    /// A new adapter was added.
+    Added(AdapterInfo),
+    /// The adapter at the given path was removed.
+    Removed(OwnedObjectPath),
+    /// Properties of a known adapter changed, e.g. it was powered down or
+    /// discovery was stopped by another process.
+    Changed(AdapterChange),
+}
+
+/// Lists every adapter known to BlueZ, ordered by object path.
+///
+/// # Arguments
+/// * `manager` - Proxy to BlueZ's object manager.
+pub async fn list_adapters(
+    manager: &crate::ObjectManagerProxy<'_>,
+) -> crate::Result<Vec<AdapterInfo>> {
+    let mut adapters: Vec<AdapterInfo> = manager
+        .get_managed_objects()
+        .await?
+        .into_iter()
+        .filter_map(|(path, interfaces)| {
+            AdapterInfo::from_properties(path, interfaces.get(ADAPTER_INTERFACE)?)
+        })
+        .collect();
+    adapters.sort_by(|a, b| a.path.as_str().cmp(b.path.as_str()));
+    Ok(adapters)
+}
+
+/// Creates a stream of adapters being added and removed.
+///
+/// This is a lightweight hotplug notification: it reports neither the
+/// adapters present when it starts, nor property changes, nor adapters that
+/// vanish because `bluetoothd` exits. Use [`crate::Monitor::adapter_events`]
+/// to keep an accurate view of every adapter's state.
+///
+/// # Arguments
+/// * `manager` - Proxy to BlueZ's object manager.
+pub async fn adapter_events(
+    manager: &crate::ObjectManagerProxy<'static>,
+) -> crate::Result<impl Stream<Item = AdapterEvent>> {
+    let added = manager
+        .receive_interfaces_added()
+        .await?
+        .filter_map(|signal| async move {
+            let args = signal.args().ok()?;
+            let properties = args.interfaces().get(ADAPTER_INTERFACE)?;
+            AdapterInfo::from_properties(args.object_path().clone(), properties)
+                .map(AdapterEvent::Added)
+        });
+
+    let removed = manager
+        .receive_interfaces_removed()
+        .await?
+        .filter_map(|signal| async move {
+            let args = signal.args().ok()?;
+            args.interfaces()
+                .iter()
+                .any(|interface| interface == ADAPTER_INTERFACE)
+                .then(|| AdapterEvent::Removed(args.object_path().clone()))
+        });
+
+    Ok(futures::stream::select(added, removed))
 }
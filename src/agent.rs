@@ -1,15 +1,232 @@
-/// Represents a Bluetooth agent that handles authentication requests.
-/// This struct manages PIN codes and passkeys for pairing operations.
-pub struct Agent {
+use std::sync::Arc;
+
+use zbus::zvariant::{ObjectPath, OwnedObjectPath};
+
+use crate::BtUuid;
+
+/// Errors a [`PairingHandler`] can answer a pairing request with.
+///
+/// They are returned to BlueZ as `org.bluez.Error.Rejected` and
+/// `org.bluez.Error.Canceled` respectively.
+#[derive(Debug, zbus::DBusError)]
+#[zbus(prefix = "org.bluez.Error")]
+pub enum PairingError {
+    /// The request was refused.
+    Rejected,
+    /// The request was aborted, e.g. because the user dismissed the prompt.
+    Canceled,
+}
+
+/// Result type returned by [`PairingHandler`] callbacks.
+pub type PairingResult<T> = Result<T, PairingError>;
+
+/// Identifies an `org.bluez.Agent1` request that depends on the agent's IO
+/// capability, see [`crate::AgentCapability::required_callbacks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PairingCallback {
+    /// Asks for the PIN code of a legacy pairing, see [`RequestPinCode`].
+    RequestPinCode,
+    /// Shows the PIN code of a legacy pairing, see [`DisplayPinCode`].
+    DisplayPinCode,
+    /// Asks for the passkey entered on the remote side, see [`RequestPasskey`].
+    RequestPasskey,
+    /// Shows the passkey to enter on the remote side, see [`DisplayPasskey`].
+    DisplayPasskey,
+    /// Asks to confirm that both sides show the same passkey, see [`RequestConfirmation`].
+    RequestConfirmation,
+}
+
+/// Decides how pairing and authorization requests received by an [`Agent`] are answered.
+///
+/// Every callback mirrors a method of `org.bluez.Agent1`. The requests that
+/// depend on the agent's IO capability are answered by the [`RequestPinCode`],
+/// [`DisplayPinCode`], [`RequestPasskey`], [`DisplayPasskey`] and
+/// [`RequestConfirmation`] traits; a handler implements the ones matching the
+/// capability it registers with and enables them on an [`AgentBuilder`].
+/// Requests that are not handled are rejected.
+#[async_trait::async_trait]
+pub trait PairingHandler: Send + Sync {
+    /// Called when BlueZ unregisters the agent.
+    async fn release(&self) {}
+
+    /// Authorizes an incoming pairing request that would otherwise be accepted without user input.
+    async fn request_authorization(&self, _device: &OwnedObjectPath) -> PairingResult<()> {
+        Err(PairingError::Rejected)
+    }
+
+    /// Authorizes `device` to connect to the service identified by `uuid`.
+    async fn authorize_service(
+        &self,
+        _device: &OwnedObjectPath,
+        _uuid: &BtUuid,
+    ) -> PairingResult<()> {
+        Err(PairingError::Rejected)
+    }
+
+    /// Called when BlueZ cancels an outstanding request.
+    async fn cancel(&self) {}
+}
+
+/// Answers [`PairingCallback::RequestPinCode`].
+#[async_trait::async_trait]
+pub trait RequestPinCode: PairingHandler {
+    /// Returns the PIN code to use for legacy pairing with `device`.
+    async fn request_pin_code(&self, device: &OwnedObjectPath) -> PairingResult<String>;
+}
+
+/// Answers [`PairingCallback::DisplayPinCode`].
+#[async_trait::async_trait]
+pub trait DisplayPinCode: PairingHandler {
+    /// Shows `pincode` to the user so it can be entered on `device`.
+    async fn display_pin_code(&self, device: &OwnedObjectPath, pincode: &str) -> PairingResult<()>;
+}
+
+/// Answers [`PairingCallback::RequestPasskey`].
+#[async_trait::async_trait]
+pub trait RequestPasskey: PairingHandler {
+    /// Returns the passkey (0-999999) to use for pairing with `device`.
+    async fn request_passkey(&self, device: &OwnedObjectPath) -> PairingResult<u32>;
+}
+
+/// Answers [`PairingCallback::DisplayPasskey`].
+#[async_trait::async_trait]
+pub trait DisplayPasskey: PairingHandler {
+    /// Shows `passkey` to the user; `entered` is the number of digits typed on the remote side.
+    async fn display_passkey(&self, device: &OwnedObjectPath, passkey: u32, entered: u16);
+}
+
+/// Answers [`PairingCallback::RequestConfirmation`].
+#[async_trait::async_trait]
+pub trait RequestConfirmation: PairingHandler {
+    /// Confirms that `passkey` matches the one shown on `device`.
+    async fn request_confirmation(
+        &self,
+        device: &OwnedObjectPath,
+        passkey: u32,
+    ) -> PairingResult<()>;
+}
+
+/// A [`PairingHandler`] that always answers with a fixed PIN code and passkey
+/// and accepts every confirmation and authorization request.
+pub struct FixedPairingHandler {
     /// The PIN code used for pairing.
     pincode: String,
     /// The passkey used for authentication.
     passkey: u32,
 }
 
+impl FixedPairingHandler {
+    /// Creates a new handler with a given PIN code and passkey.
+    ///
+    /// # Arguments
+    /// * `pincode` - A string representing the PIN code.
+    /// * `passkey` - A 32-bit unsigned integer representing the passkey.
+    ///
+    /// # Returns
+    /// A new `FixedPairingHandler` instance.
+    pub fn new(pincode: String, passkey: u32) -> Self {
+        Self { pincode, passkey }
+    }
+}
+
+#[async_trait::async_trait]
+impl PairingHandler for FixedPairingHandler {
+    async fn release(&self) {
+        println!("Agent Released");
+    }
+
+    async fn request_authorization(&self, device: &OwnedObjectPath) -> PairingResult<()> {
+        println!("RequestAuthorization -> dev: {}", device);
+        Ok(())
+    }
+
+    async fn authorize_service(
+        &self,
+        device: &OwnedObjectPath,
+        uuid: &BtUuid,
+    ) -> PairingResult<()> {
+        println!("AuthorizeService -> dev: {}, uuid: {}", device, uuid);
+        Ok(())
+    }
+
+    async fn cancel(&self) {
+        println!("Cancel");
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestPinCode for FixedPairingHandler {
+    async fn request_pin_code(&self, device: &OwnedObjectPath) -> PairingResult<String> {
+        println!(
+            "RequestPinCode -> device: {}, pincode: {}",
+            device, self.pincode
+        );
+        Ok(self.pincode.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl DisplayPinCode for FixedPairingHandler {
+    async fn display_pin_code(&self, device: &OwnedObjectPath, pincode: &str) -> PairingResult<()> {
+        println!("DisplayPinCode -> dev: {}, pincode: {}", device, pincode);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestPasskey for FixedPairingHandler {
+    async fn request_passkey(&self, device: &OwnedObjectPath) -> PairingResult<u32> {
+        println!(
+            "RequestPasskey -> dev: {}, passkey: {}",
+            device, self.passkey
+        );
+        Ok(self.passkey)
+    }
+}
+
+#[async_trait::async_trait]
+impl DisplayPasskey for FixedPairingHandler {
+    async fn display_passkey(&self, device: &OwnedObjectPath, passkey: u32, entered: u16) {
+        println!(
+            "DisplayPasskey -> dev: {}, passkey: {}, entered: {}",
+            device, passkey, entered
+        );
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestConfirmation for FixedPairingHandler {
+    async fn request_confirmation(
+        &self,
+        device: &OwnedObjectPath,
+        passkey: u32,
+    ) -> PairingResult<()> {
+        println!(
+            "RequestConfirmation -> dev: {}, passkey: {}",
+            device, passkey
+        );
+        Ok(())
+    }
+}
+
+/// Represents a Bluetooth agent that handles authentication requests.
+/// The agent is exported on the bus as `org.bluez.Agent1` and forwards every
+/// request to its [`PairingHandler`].
+pub struct Agent {
+    /// The handler deciding how requests are answered.
+    handler: Arc<dyn PairingHandler>,
+    request_pin_code: Option<Arc<dyn RequestPinCode>>,
+    display_pin_code: Option<Arc<dyn DisplayPinCode>>,
+    request_passkey: Option<Arc<dyn RequestPasskey>>,
+    display_passkey: Option<Arc<dyn DisplayPasskey>>,
+    request_confirmation: Option<Arc<dyn RequestConfirmation>>,
+}
+
 impl Agent {
     /// Creates a new agent with a given PIN code and passkey.
     ///
+    /// The agent answers every request with a [`FixedPairingHandler`].
+    ///
     /// # Arguments
     /// * `pincode` - A string representing the PIN code.
     /// * `passkey` - A 32-bit unsigned integer representing the passkey.
@@ -17,16 +234,138 @@ impl Agent {
     /// # Returns
     /// A new `Agent` instance.
     pub fn new(pincode: String, passkey: u32) -> Self {
-        Self { pincode, passkey }
+        Self::builder(FixedPairingHandler::new(pincode, passkey))
+            .request_pin_code()
+            .display_pin_code()
+            .request_passkey()
+            .display_passkey()
+            .request_confirmation()
+            .build()
     }
 
-    /// Releases the agent, indicating it is no longer in use.
+    /// Creates a builder for an agent answering requests through `handler`.
     ///
-    /// # Returns
-    /// A `Result` indicating success or failure.
-    pub async fn release(&self) -> zbus::fdo::Result<()> {
-        println!("Agent Released");
-        Ok(())
+    /// # Arguments
+    /// * `handler` - The pairing handler deciding each request.
+    pub fn builder<H: PairingHandler + 'static>(handler: H) -> AgentBuilder<H> {
+        AgentBuilder {
+            handler: Arc::new(handler),
+            request_pin_code: None,
+            display_pin_code: None,
+            request_passkey: None,
+            display_passkey: None,
+            request_confirmation: None,
+        }
+    }
+
+    /// Lists the callbacks the agent answers.
+    pub fn callbacks(&self) -> Vec<PairingCallback> {
+        [
+            (
+                PairingCallback::RequestPinCode,
+                self.request_pin_code.is_some(),
+            ),
+            (
+                PairingCallback::DisplayPinCode,
+                self.display_pin_code.is_some(),
+            ),
+            (
+                PairingCallback::RequestPasskey,
+                self.request_passkey.is_some(),
+            ),
+            (
+                PairingCallback::DisplayPasskey,
+                self.display_passkey.is_some(),
+            ),
+            (
+                PairingCallback::RequestConfirmation,
+                self.request_confirmation.is_some(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(callback, handled)| handled.then_some(callback))
+        .collect()
+    }
+}
+
+/// Builds an [`Agent`], enabling the callbacks its handler implements.
+///
+/// Each callback can only be enabled if the handler implements its trait, so
+/// the callbacks checked against an [`crate::AgentCapability`] are exactly
+/// the ones the agent answers.
+pub struct AgentBuilder<H> {
+    handler: Arc<H>,
+    request_pin_code: Option<Arc<dyn RequestPinCode>>,
+    display_pin_code: Option<Arc<dyn DisplayPinCode>>,
+    request_passkey: Option<Arc<dyn RequestPasskey>>,
+    display_passkey: Option<Arc<dyn DisplayPasskey>>,
+    request_confirmation: Option<Arc<dyn RequestConfirmation>>,
+}
+
+impl<H: PairingHandler + 'static> AgentBuilder<H> {
+    /// Answers `RequestPinCode` with the handler.
+    pub fn request_pin_code(mut self) -> Self
+    where
+        H: RequestPinCode,
+    {
+        self.request_pin_code = Some(self.handler.clone());
+        self
+    }
+
+    /// Answers `DisplayPinCode` with the handler.
+    pub fn display_pin_code(mut self) -> Self
+    where
+        H: DisplayPinCode,
+    {
+        self.display_pin_code = Some(self.handler.clone());
+        self
+    }
+
+    /// Answers `RequestPasskey` with the handler.
+    pub fn request_passkey(mut self) -> Self
+    where
+        H: RequestPasskey,
+    {
+        self.request_passkey = Some(self.handler.clone());
+        self
+    }
+
+    /// Answers `DisplayPasskey` with the handler.
+    pub fn display_passkey(mut self) -> Self
+    where
+        H: DisplayPasskey,
+    {
+        self.display_passkey = Some(self.handler.clone());
+        self
+    }
+
+    /// Answers `RequestConfirmation` with the handler.
+    pub fn request_confirmation(mut self) -> Self
+    where
+        H: RequestConfirmation,
+    {
+        self.request_confirmation = Some(self.handler.clone());
+        self
+    }
+
+    /// Creates the agent.
+    pub fn build(self) -> Agent {
+        Agent {
+            handler: self.handler,
+            request_pin_code: self.request_pin_code,
+            display_pin_code: self.display_pin_code,
+            request_passkey: self.request_passkey,
+            display_passkey: self.display_passkey,
+            request_confirmation: self.request_confirmation,
+        }
+    }
+}
+
+#[zbus::interface(name = "org.bluez.Agent1")]
+impl Agent {
+    /// Releases the agent, indicating it is no longer in use.
+    async fn release(&self) {
+        self.handler.release().await
     }
 
     /// Handles a request for a PIN code from a Bluetooth device.
@@ -36,15 +375,11 @@ impl Agent {
     ///
     /// # Returns
     /// The PIN code as a string.
-    pub async fn request_pin_code(
-        &self,
-        device: &zbus::zvariant::OwnedObjectPath,
-    ) -> zbus::fdo::Result<String> {
-        println!(
-            "RequestPinCode -> device: {}, pincode: {}",
-            device, self.pincode
-        );
-        Ok(self.pincode.clone())
+    async fn request_pin_code(&self, device: ObjectPath<'_>) -> Result<String, PairingError> {
+        match &self.request_pin_code {
+            Some(handler) => handler.request_pin_code(&device.into()).await,
+            None => Err(PairingError::Rejected),
+        }
     }
 
     /// Displays a PIN code on the device for user verification.
@@ -52,13 +387,15 @@ impl Agent {
     /// # Arguments
     /// * `device` - The D-Bus object path of the device.
     /// * `pincode` - The PIN code to display.
-    pub async fn display_pin_code(
+    async fn display_pin_code(
         &self,
-        device: &zbus::zvariant::OwnedObjectPath,
+        device: ObjectPath<'_>,
         pincode: &str,
-    ) -> zbus::fdo::Result<()> {
-        println!("DisplayPinCode -> dev: {}, pincode: {}", device, pincode);
-        Ok(())
+    ) -> Result<(), PairingError> {
+        match &self.display_pin_code {
+            Some(handler) => handler.display_pin_code(&device.into(), pincode).await,
+            None => Err(PairingError::Rejected),
+        }
     }
 
     /// Handles a request for a passkey from a Bluetooth device.
@@ -68,15 +405,11 @@ impl Agent {
     ///
     /// # Returns
     /// The passkey as a 32-bit unsigned integer.
-    pub async fn request_passkey(
-        &self,
-        device: &zbus::zvariant::OwnedObjectPath,
-    ) -> zbus::fdo::Result<u32> {
-        println!(
-            "RequestPasskey -> dev: {}, passkey: {}",
-            device, self.passkey
-        );
-        Ok(self.passkey)
+    async fn request_passkey(&self, device: ObjectPath<'_>) -> Result<u32, PairingError> {
+        match &self.request_passkey {
+            Some(handler) => handler.request_passkey(&device.into()).await,
+            None => Err(PairingError::Rejected),
+        }
     }
 
     /// Displays the passkey and entered digits on the device.
@@ -85,17 +418,12 @@ impl Agent {
     /// * `device` - The D-Bus object path of the device.
     /// * `passkey` - The passkey to display.
     /// * `entered` - The number of entered digits.
-    pub async fn display_passkey(
-        &self,
-        device: &zbus::zvariant::OwnedObjectPath,
-        passkey: u32,
-        entered: u16,
-    ) -> zbus::fdo::Result<()> {
-        println!(
-            "DisplayPasskey -> dev: {}, passkey: {}, entered: {}",
-            device, passkey, entered
-        );
-        Ok(())
+    async fn display_passkey(&self, device: ObjectPath<'_>, passkey: u32, entered: u16) {
+        if let Some(handler) = &self.display_passkey {
+            handler
+                .display_passkey(&device.into(), passkey, entered)
+                .await
+        }
     }
 
     /// Requests user confirmation for pairing based on the passkey.
@@ -103,47 +431,42 @@ impl Agent {
     /// # Arguments
     /// * `device` - The D-Bus object path of the device.
     /// * `passkey` - The passkey for verification.
-    pub fn request_confirmation(
+    async fn request_confirmation(
         &self,
-        device: &zbus::zvariant::OwnedObjectPath,
+        device: ObjectPath<'_>,
         passkey: u32,
-    ) -> zbus::fdo::Result<()> {
-        println!(
-            "RequestConfirmation -> dev: {}, passkey: {}",
-            device, passkey
-        );
-        Ok(())
+    ) -> Result<(), PairingError> {
+        match &self.request_confirmation {
+            Some(handler) => handler.request_confirmation(&device.into(), passkey).await,
+            None => Err(PairingError::Rejected),
+        }
     }
 
     /// Requests user authorization for a connection.
     ///
     /// # Arguments
     /// * `device` - The D-Bus object path of the device.
-    pub async fn request_authorization(
-        &self,
-        device: &zbus::zvariant::OwnedObjectPath,
-    ) -> zbus::fdo::Result<()> {
-        println!("RequestAuthorization -> dev: {}", device);
-        Ok(())
+    async fn request_authorization(&self, device: ObjectPath<'_>) -> Result<(), PairingError> {
+        self.handler.request_authorization(&device.into()).await
     }
 
     /// Authorizes a service request from a Bluetooth device.
+    /// Requests for malformed UUIDs are rejected.
     ///
     /// # Arguments
     /// * `device` - The D-Bus object path of the device.
     /// * `uuid` - The UUID of the requested service.
-    pub async fn authorize_service(
+    async fn authorize_service(
         &self,
-        device: &zbus::zvariant::OwnedObjectPath,
+        device: ObjectPath<'_>,
         uuid: &str,
-    ) -> zbus::fdo::Result<()> {
-        println!("AuthorizeService -> dev: {}, uuid: {}", device, uuid);
-        Ok(())
+    ) -> Result<(), PairingError> {
+        let uuid: BtUuid = uuid.parse().map_err(|_| PairingError::Rejected)?;
+        self.handler.authorize_service(&device.into(), &uuid).await
     }
 
     /// Cancels an ongoing pairing or authentication process.
-    pub async fn cancel(&self) -> zbus::fdo::Result<()> {
-        println!("Cancel");
-        Ok(())
+    async fn cancel(&self) {
+        self.handler.cancel().await
     }
 }
@@ -1,10 +1,228 @@
-#[zbus::proxy(default_service = "org.bluez", interface = "org.bluez.AgentManager1")]
+use zbus::object_server::Interface;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::agent::{Agent, PairingCallback};
+
+/// The IO capability an agent is registered with.
+///
+/// It tells BlueZ which pairing method can be used and therefore which
+/// [`PairingCallback`]s the agent's handler has to implement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AgentCapability {
+    /// Can show a PIN code or passkey but take no input.
+    DisplayOnly,
+    /// Can show a passkey and let the user answer yes or no.
+    DisplayYesNo,
+    /// Can take a PIN code or passkey typed by the user but show nothing.
+    KeyboardOnly,
+    /// Has no way to interact with the user; pairing uses Just Works.
+    NoInputNoOutput,
+    /// Can both show and take a PIN code or passkey.
+    KeyboardDisplay,
+}
+
+impl AgentCapability {
+    /// Returns the capability string expected by `org.bluez.AgentManager1`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgentCapability::DisplayOnly => "DisplayOnly",
+            AgentCapability::DisplayYesNo => "DisplayYesNo",
+            AgentCapability::KeyboardOnly => "KeyboardOnly",
+            AgentCapability::NoInputNoOutput => "NoInputNoOutput",
+            AgentCapability::KeyboardDisplay => "KeyboardDisplay",
+        }
+    }
+
+    /// Returns the callbacks BlueZ may invoke for this capability.
+    pub fn required_callbacks(&self) -> &'static [PairingCallback] {
+        match self {
+            AgentCapability::DisplayOnly => &[
+                PairingCallback::DisplayPinCode,
+                PairingCallback::DisplayPasskey,
+            ],
+            AgentCapability::DisplayYesNo => &[
+                PairingCallback::DisplayPinCode,
+                PairingCallback::DisplayPasskey,
+                PairingCallback::RequestConfirmation,
+            ],
+            AgentCapability::KeyboardOnly => &[
+                PairingCallback::RequestPinCode,
+                PairingCallback::RequestPasskey,
+            ],
+            AgentCapability::NoInputNoOutput => &[],
+            AgentCapability::KeyboardDisplay => &[
+                PairingCallback::RequestPinCode,
+                PairingCallback::DisplayPinCode,
+                PairingCallback::RequestPasskey,
+                PairingCallback::DisplayPasskey,
+                PairingCallback::RequestConfirmation,
+            ],
+        }
+    }
+
+    /// Returns the required callbacks missing from `implemented`.
+    ///
+    /// # Arguments
+    /// * `implemented` - The callbacks a handler implements.
+    pub fn missing_callbacks(&self, implemented: &[PairingCallback]) -> Vec<PairingCallback> {
+        self.required_callbacks()
+            .iter()
+            .filter(|callback| !implemented.contains(callback))
+            .copied()
+            .collect()
+    }
+}
+
+impl std::fmt::Display for AgentCapability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Defines the `AgentManager` trait for registering pairing agents with BlueZ.
+#[zbus::proxy(
+    default_service = "org.bluez",
+    interface = "org.bluez.AgentManager1",
+    default_path = "/org/bluez"
+)]
 pub trait AgentManager {
+    /// Registers an agent object exported at `path` with the given IO capability.
     fn register_agent(
         &self,
         path: &zbus::zvariant::OwnedObjectPath,
         capability: String,
-    ) -> zbus::Result<()>;
-    fn unregister_agent(&self, path: &zbus::zvariant::OwnedObjectPath) -> zbus::Result<()>;
-    fn request_default_agent(&self, path: &zbus::zvariant::OwnedObjectPath) -> zbus::Result<()>;
+    ) -> crate::Result<()>;
+
+    /// Unregisters a previously registered agent.
+    fn unregister_agent(&self, path: &zbus::zvariant::OwnedObjectPath) -> crate::Result<()>;
+
+    /// Makes a registered agent the default one for the system.
+    fn request_default_agent(&self, path: &zbus::zvariant::OwnedObjectPath) -> crate::Result<()>;
+}
+
+/// A pairing agent that has been exported on the bus and registered with BlueZ.
+///
+/// The agent is unregistered and removed from the object server when
+/// [`AgentRegistration::unregister`] is called or, as a best effort, when the
+/// registration is dropped inside a tokio runtime.
+pub struct AgentRegistration {
+    /// Connection the agent is served on.
+    connection: zbus::Connection,
+    /// Object path the agent is exported at.
+    path: OwnedObjectPath,
+    /// Interface name the agent was served with.
+    interface: zbus::names::InterfaceName<'static>,
+    /// Whether the agent is still registered.
+    registered: bool,
+}
+
+impl AgentRegistration {
+    /// Returns the object path the agent is exported at.
+    pub fn path(&self) -> &OwnedObjectPath {
+        &self.path
+    }
+
+    /// Unregisters the agent from BlueZ and removes it from the object server.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or failure.
+    pub async fn unregister(mut self) -> crate::Result<()> {
+        self.registered = false;
+        release_agent(&self.connection, &self.path, &self.interface).await
+    }
+}
+
+impl Drop for AgentRegistration {
+    fn drop(&mut self) {
+        if !self.registered {
+            return;
+        }
+
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let connection = self.connection.clone();
+            let path = self.path.clone();
+            let interface = self.interface.clone();
+            handle.spawn(async move {
+                let _ = release_agent(&connection, &path, &interface).await;
+            });
+        }
+    }
+}
+
+/// Serves `agent` at `path` and registers it with BlueZ.
+///
+/// The agent's handler must implement every callback required by `capability`,
+/// otherwise registration fails before anything is exported.
+///
+/// # Arguments
+/// * `connection` - The system bus connection.
+/// * `path` - The object path to export the agent at.
+/// * `agent` - The agent answering `org.bluez.Agent1` requests.
+/// * `capability` - The IO capability to register with.
+/// * `default` - Whether to also request to become the default agent.
+///
+/// # Returns
+/// An [`AgentRegistration`] that unregisters the agent when released.
+pub async fn register_agent(
+    connection: &zbus::Connection,
+    path: &str,
+    agent: Agent,
+    capability: AgentCapability,
+    default: bool,
+) -> crate::Result<AgentRegistration> {
+    let missing = capability.missing_callbacks(&agent.callbacks());
+    if !missing.is_empty() {
+        return Err(crate::Error::InvalidArguments(format!(
+            "agent capability {} requires unimplemented callbacks {:?}",
+            capability, missing
+        )));
+    }
+
+    let path = OwnedObjectPath::try_from(path)?;
+    connection.object_server().at(path.as_ref(), agent).await?;
+
+    let registration = AgentRegistration {
+        connection: connection.clone(),
+        path,
+        interface: Agent::name(),
+        registered: true,
+    };
+
+    announce_agent(connection, &registration.path, capability, default).await?;
+
+    Ok(registration)
+}
+
+/// Registers the agent already exported at `path` with BlueZ.
+pub(crate) async fn announce_agent(
+    connection: &zbus::Connection,
+    path: &OwnedObjectPath,
+    capability: AgentCapability,
+    default: bool,
+) -> crate::Result<()> {
+    let manager = AgentManagerProxy::new(connection).await?;
+    manager
+        .register_agent(path, capability.as_str().to_string())
+        .await?;
+
+    if default {
+        manager.request_default_agent(path).await?;
+    }
+
+    Ok(())
+}
+
+/// Unregisters the agent at `path` and removes it from the object server.
+async fn release_agent(
+    connection: &zbus::Connection,
+    path: &OwnedObjectPath,
+    interface: &zbus::names::InterfaceName<'static>,
+) -> crate::Result<()> {
+    let manager = AgentManagerProxy::new(connection).await?;
+    let result = manager.unregister_agent(path).await;
+    connection
+        .object_server()
+        .remove_named(path.as_ref(), interface.clone())
+        .await?;
+    result
 }
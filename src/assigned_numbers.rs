@@ -0,0 +1,895 @@
+//! Names of Bluetooth SIG assigned numbers, embedded when the
+//! `assigned-numbers` feature is enabled.
+//!
+//! The tables are a subset of the numbers the SIG publishes, not a complete copy:
+//!
+//! - services: the classic service classes, the GATT services `0x1800` to
+//!   `0x1859` and a few member UUIDs;
+//! - characteristics: `0x2a00` to `0x2ade` and a few later ones;
+//! - descriptors: `0x2900` to `0x2911`;
+//! - companies: the identifiers up to `0x00e0` and a few widely deployed vendors;
+//! - appearances: the categories up to `0x037` and `0x051`, with the
+//!   subcategories of the common ones;
+//! - Class of Device: the major classes and minor classes of the baseband
+//!   assigned numbers.
+//!
+//! Numbers missing from the tables return `None`.
+
+use crate::BtUuid;
+
+/// Returns the name of a GATT service or Bluetooth service class, e.g. "Heart Rate".
+pub fn service_name(uuid: &BtUuid) -> Option<&'static str> {
+    Some(match uuid.as_u16()? {
+        0x1000 => "Service Discovery Server",
+        0x1001 => "Browse Group Descriptor",
+        0x1101 => "Serial Port",
+        0x1102 => "LAN Access Using PPP",
+        0x1103 => "Dialup Networking",
+        0x1104 => "IrMC Sync",
+        0x1105 => "OBEX Object Push",
+        0x1106 => "OBEX File Transfer",
+        0x1107 => "IrMC Sync Command",
+        0x1108 => "Headset",
+        0x1109 => "Cordless Telephony",
+        0x110a => "Audio Source",
+        0x110b => "Audio Sink",
+        0x110c => "A/V Remote Control Target",
+        0x110d => "Advanced Audio Distribution",
+        0x110e => "A/V Remote Control",
+        0x110f => "A/V Remote Control Controller",
+        0x1110 => "Intercom",
+        0x1111 => "Fax",
+        0x1112 => "Headset - Audio Gateway",
+        0x1113 => "WAP",
+        0x1114 => "WAP Client",
+        0x1115 => "PANU",
+        0x1116 => "NAP",
+        0x1117 => "GN",
+        0x1118 => "Direct Printing",
+        0x1119 => "Reference Printing",
+        0x111a => "Basic Imaging Profile",
+        0x111b => "Imaging Responder",
+        0x111c => "Imaging Automatic Archive",
+        0x111d => "Imaging Referenced Objects",
+        0x111e => "Handsfree",
+        0x111f => "Handsfree Audio Gateway",
+        0x1120 => "Direct Printing Reference Objects Service",
+        0x1121 => "Reflected UI",
+        0x1122 => "Basic Printing",
+        0x1123 => "Printing Status",
+        0x1124 => "Human Interface Device Service",
+        0x1125 => "Hardcopy Cable Replacement",
+        0x1126 => "HCR Print",
+        0x1127 => "HCR Scan",
+        0x1128 => "Common ISDN Access",
+        0x112d => "SIM Access",
+        0x112e => "Phonebook Access - PCE",
+        0x112f => "Phonebook Access - PSE",
+        0x1130 => "Phonebook Access",
+        0x1131 => "Headset - HS",
+        0x1132 => "Message Access Server",
+        0x1133 => "Message Notification Server",
+        0x1134 => "Message Access Profile",
+        0x1135 => "GNSS",
+        0x1136 => "GNSS Server",
+        0x1137 => "3D Display",
+        0x1138 => "3D Glasses",
+        0x1139 => "3D Synchronization",
+        0x113a => "MPS Profile",
+        0x113b => "MPS Class",
+        0x113c => "CTN Access Service",
+        0x113d => "CTN Notification Service",
+        0x113e => "CTN Profile",
+        0x1200 => "PnP Information",
+        0x1201 => "Generic Networking",
+        0x1202 => "Generic File Transfer",
+        0x1203 => "Generic Audio",
+        0x1204 => "Generic Telephony",
+        0x1205 => "UPNP Service",
+        0x1206 => "UPNP IP Service",
+        0x1300 => "ESDP UPNP IP PAN",
+        0x1301 => "ESDP UPNP IP LAP",
+        0x1302 => "ESDP UPNP L2CAP",
+        0x1303 => "Video Source",
+        0x1304 => "Video Sink",
+        0x1305 => "Video Distribution",
+        0x1400 => "HDP",
+        0x1401 => "HDP Source",
+        0x1402 => "HDP Sink",
+        0x1800 => "Generic Access",
+        0x1801 => "Generic Attribute",
+        0x1802 => "Immediate Alert",
+        0x1803 => "Link Loss",
+        0x1804 => "Tx Power",
+        0x1805 => "Current Time",
+        0x1806 => "Reference Time Update",
+        0x1807 => "Next DST Change",
+        0x1808 => "Glucose",
+        0x1809 => "Health Thermometer",
+        0x180a => "Device Information",
+        0x180d => "Heart Rate",
+        0x180e => "Phone Alert Status",
+        0x180f => "Battery",
+        0x1810 => "Blood Pressure",
+        0x1811 => "Alert Notification",
+        0x1812 => "Human Interface Device",
+        0x1813 => "Scan Parameters",
+        0x1814 => "Running Speed and Cadence",
+        0x1815 => "Automation IO",
+        0x1816 => "Cycling Speed and Cadence",
+        0x1818 => "Cycling Power",
+        0x1819 => "Location and Navigation",
+        0x181a => "Environmental Sensing",
+        0x181b => "Body Composition",
+        0x181c => "User Data",
+        0x181d => "Weight Scale",
+        0x181e => "Bond Management",
+        0x181f => "Continuous Glucose Monitoring",
+        0x1820 => "Internet Protocol Support",
+        0x1821 => "Indoor Positioning",
+        0x1822 => "Pulse Oximeter",
+        0x1823 => "HTTP Proxy",
+        0x1824 => "Transport Discovery",
+        0x1825 => "Object Transfer",
+        0x1826 => "Fitness Machine",
+        0x1827 => "Mesh Provisioning",
+        0x1828 => "Mesh Proxy",
+        0x1829 => "Reconnection Configuration",
+        0x183a => "Insulin Delivery",
+        0x183b => "Binary Sensor",
+        0x183c => "Emergency Configuration",
+        0x183d => "Authorization Control",
+        0x183e => "Physical Activity Monitor",
+        0x183f => "Elapsed Time",
+        0x1840 => "Generic Health Sensor",
+        0x1843 => "Audio Input Control",
+        0x1844 => "Volume Control",
+        0x1845 => "Volume Offset Control",
+        0x1846 => "Coordinated Set Identification",
+        0x1847 => "Device Time",
+        0x1848 => "Media Control",
+        0x1849 => "Generic Media Control",
+        0x184a => "Constant Tone Extension",
+        0x184b => "Telephone Bearer",
+        0x184c => "Generic Telephone Bearer",
+        0x184d => "Microphone Control",
+        0x184e => "Audio Stream Control",
+        0x184f => "Broadcast Audio Scan",
+        0x1850 => "Published Audio Capabilities",
+        0x1851 => "Basic Audio Announcement",
+        0x1852 => "Broadcast Audio Announcement",
+        0x1853 => "Common Audio",
+        0x1854 => "Hearing Access",
+        0x1855 => "Telephony and Media Audio",
+        0x1856 => "Public Broadcast Announcement",
+        0x1857 => "Electronic Shelf Label",
+        0x1858 => "Gaming Audio",
+        0x1859 => "Mesh Proxy Solicitation",
+        0xfe2c | 0xfe9f | 0xfeaa => "Google LLC",
+        _ => return None,
+    })
+}
+
+/// Returns the name of a GATT characteristic, e.g. "Battery Level".
+pub fn characteristic_name(uuid: &BtUuid) -> Option<&'static str> {
+    Some(match uuid.as_u16()? {
+        0x2a00 => "Device Name",
+        0x2a01 => "Appearance",
+        0x2a02 => "Peripheral Privacy Flag",
+        0x2a03 => "Reconnection Address",
+        0x2a04 => "Peripheral Preferred Connection Parameters",
+        0x2a05 => "Service Changed",
+        0x2a06 => "Alert Level",
+        0x2a07 => "Tx Power Level",
+        0x2a08 => "Date Time",
+        0x2a09 => "Day of Week",
+        0x2a0a => "Day Date Time",
+        0x2a0c => "Exact Time 256",
+        0x2a0d => "DST Offset",
+        0x2a0e => "Time Zone",
+        0x2a0f => "Local Time Information",
+        0x2a11 => "Time with DST",
+        0x2a12 => "Time Accuracy",
+        0x2a13 => "Time Source",
+        0x2a14 => "Reference Time Information",
+        0x2a16 => "Time Update Control Point",
+        0x2a17 => "Time Update State",
+        0x2a18 => "Glucose Measurement",
+        0x2a19 => "Battery Level",
+        0x2a1c => "Temperature Measurement",
+        0x2a1d => "Temperature Type",
+        0x2a1e => "Intermediate Temperature",
+        0x2a21 => "Measurement Interval",
+        0x2a22 => "Boot Keyboard Input Report",
+        0x2a23 => "System ID",
+        0x2a24 => "Model Number String",
+        0x2a25 => "Serial Number String",
+        0x2a26 => "Firmware Revision String",
+        0x2a27 => "Hardware Revision String",
+        0x2a28 => "Software Revision String",
+        0x2a29 => "Manufacturer Name String",
+        0x2a2a => "IEEE 11073-20601 Regulatory Certification Data List",
+        0x2a2b => "Current Time",
+        0x2a31 => "Scan Refresh",
+        0x2a32 => "Boot Keyboard Output Report",
+        0x2a33 => "Boot Mouse Input Report",
+        0x2a34 => "Glucose Measurement Context",
+        0x2a35 => "Blood Pressure Measurement",
+        0x2a36 => "Intermediate Cuff Pressure",
+        0x2a37 => "Heart Rate Measurement",
+        0x2a38 => "Body Sensor Location",
+        0x2a39 => "Heart Rate Control Point",
+        0x2a3f => "Alert Status",
+        0x2a40 => "Ringer Control Point",
+        0x2a41 => "Ringer Setting",
+        0x2a42 => "Alert Category ID Bit Mask",
+        0x2a43 => "Alert Category ID",
+        0x2a44 => "Alert Notification Control Point",
+        0x2a45 => "Unread Alert Status",
+        0x2a46 => "New Alert",
+        0x2a47 => "Supported New Alert Category",
+        0x2a48 => "Supported Unread Alert Category",
+        0x2a49 => "Blood Pressure Feature",
+        0x2a4a => "HID Information",
+        0x2a4b => "Report Map",
+        0x2a4c => "HID Control Point",
+        0x2a4d => "Report",
+        0x2a4e => "Protocol Mode",
+        0x2a4f => "Scan Interval Window",
+        0x2a50 => "PnP ID",
+        0x2a51 => "Glucose Feature",
+        0x2a52 => "Record Access Control Point",
+        0x2a53 => "RSC Measurement",
+        0x2a54 => "RSC Feature",
+        0x2a55 => "SC Control Point",
+        0x2a5a => "Aggregate",
+        0x2a5b => "CSC Measurement",
+        0x2a5c => "CSC Feature",
+        0x2a5d => "Sensor Location",
+        0x2a5e => "PLX Spot-Check Measurement",
+        0x2a5f => "PLX Continuous Measurement",
+        0x2a60 => "PLX Features",
+        0x2a63 => "Cycling Power Measurement",
+        0x2a64 => "Cycling Power Vector",
+        0x2a65 => "Cycling Power Feature",
+        0x2a66 => "Cycling Power Control Point",
+        0x2a67 => "Location and Speed",
+        0x2a68 => "Navigation",
+        0x2a69 => "Position Quality",
+        0x2a6a => "LN Feature",
+        0x2a6b => "LN Control Point",
+        0x2a6c => "Elevation",
+        0x2a6d => "Pressure",
+        0x2a6e => "Temperature",
+        0x2a6f => "Humidity",
+        0x2a70 => "True Wind Speed",
+        0x2a71 => "True Wind Direction",
+        0x2a72 => "Apparent Wind Speed",
+        0x2a73 => "Apparent Wind Direction",
+        0x2a74 => "Gust Factor",
+        0x2a75 => "Pollen Concentration",
+        0x2a76 => "UV Index",
+        0x2a77 => "Irradiance",
+        0x2a78 => "Rainfall",
+        0x2a79 => "Wind Chill",
+        0x2a7a => "Heat Index",
+        0x2a7b => "Dew Point",
+        0x2a7d => "Descriptor Value Changed",
+        0x2a7e => "Aerobic Heart Rate Lower Limit",
+        0x2a7f => "Aerobic Threshold",
+        0x2a80 => "Age",
+        0x2a81 => "Anaerobic Heart Rate Lower Limit",
+        0x2a82 => "Anaerobic Heart Rate Upper Limit",
+        0x2a83 => "Anaerobic Threshold",
+        0x2a84 => "Aerobic Heart Rate Upper Limit",
+        0x2a85 => "Date of Birth",
+        0x2a86 => "Date of Threshold Assessment",
+        0x2a87 => "Email Address",
+        0x2a88 => "Fat Burn Heart Rate Lower Limit",
+        0x2a89 => "Fat Burn Heart Rate Upper Limit",
+        0x2a8a => "First Name",
+        0x2a8b => "Five Zone Heart Rate Limits",
+        0x2a8c => "Gender",
+        0x2a8d => "Heart Rate Max",
+        0x2a8e => "Height",
+        0x2a8f => "Hip Circumference",
+        0x2a90 => "Last Name",
+        0x2a91 => "Maximum Recommended Heart Rate",
+        0x2a92 => "Resting Heart Rate",
+        0x2a93 => "Sport Type for Aerobic and Anaerobic Thresholds",
+        0x2a94 => "Three Zone Heart Rate Limits",
+        0x2a95 => "Two Zone Heart Rate Limits",
+        0x2a96 => "VO2 Max",
+        0x2a97 => "Waist Circumference",
+        0x2a98 => "Weight",
+        0x2a99 => "Database Change Increment",
+        0x2a9a => "User Index",
+        0x2a9b => "Body Composition Feature",
+        0x2a9c => "Body Composition Measurement",
+        0x2a9d => "Weight Measurement",
+        0x2a9e => "Weight Scale Feature",
+        0x2a9f => "User Control Point",
+        0x2aa0 => "Magnetic Flux Density - 2D",
+        0x2aa1 => "Magnetic Flux Density - 3D",
+        0x2aa2 => "Language",
+        0x2aa3 => "Barometric Pressure Trend",
+        0x2aa4 => "Bond Management Control Point",
+        0x2aa5 => "Bond Management Feature",
+        0x2aa6 => "Central Address Resolution",
+        0x2aa7 => "CGM Measurement",
+        0x2aa8 => "CGM Feature",
+        0x2aa9 => "CGM Status",
+        0x2aaa => "CGM Session Start Time",
+        0x2aab => "CGM Session Run Time",
+        0x2aac => "CGM Specific Ops Control Point",
+        0x2aad => "Indoor Positioning Configuration",
+        0x2aae => "Latitude",
+        0x2aaf => "Longitude",
+        0x2ab0 => "Local North Coordinate",
+        0x2ab1 => "Local East Coordinate",
+        0x2ab2 => "Floor Number",
+        0x2ab3 => "Altitude",
+        0x2ab4 => "Uncertainty",
+        0x2ab5 => "Location Name",
+        0x2ab6 => "URI",
+        0x2ab7 => "HTTP Headers",
+        0x2ab8 => "HTTP Status Code",
+        0x2ab9 => "HTTP Entity Body",
+        0x2aba => "HTTP Control Point",
+        0x2abb => "HTTPS Security",
+        0x2abc => "TDS Control Point",
+        0x2abd => "OTS Feature",
+        0x2abe => "Object Name",
+        0x2abf => "Object Type",
+        0x2ac0 => "Object Size",
+        0x2ac1 => "Object First-Created",
+        0x2ac2 => "Object Last-Modified",
+        0x2ac3 => "Object ID",
+        0x2ac4 => "Object Properties",
+        0x2ac5 => "Object Action Control Point",
+        0x2ac6 => "Object List Control Point",
+        0x2ac7 => "Object List Filter",
+        0x2ac8 => "Object Changed",
+        0x2ac9 => "Resolvable Private Address Only",
+        0x2acc => "Fitness Machine Feature",
+        0x2acd => "Treadmill Data",
+        0x2ace => "Cross Trainer Data",
+        0x2acf => "Step Climber Data",
+        0x2ad0 => "Stair Climber Data",
+        0x2ad1 => "Rower Data",
+        0x2ad2 => "Indoor Bike Data",
+        0x2ad3 => "Training Status",
+        0x2ad4 => "Supported Speed Range",
+        0x2ad5 => "Supported Inclination Range",
+        0x2ad6 => "Supported Resistance Level Range",
+        0x2ad7 => "Supported Heart Rate Range",
+        0x2ad8 => "Supported Power Range",
+        0x2ad9 => "Fitness Machine Control Point",
+        0x2ada => "Fitness Machine Status",
+        0x2adb => "Mesh Provisioning Data In",
+        0x2adc => "Mesh Provisioning Data Out",
+        0x2add => "Mesh Proxy Data In",
+        0x2ade => "Mesh Proxy Data Out",
+        0x2b29 => "Client Supported Features",
+        0x2b2a => "Database Hash",
+        0x2b3a => "Server Supported Features",
+        _ => return None,
+    })
+}
+
+/// Returns the name of a GATT descriptor, e.g. "Client Characteristic Configuration".
+pub fn descriptor_name(uuid: &BtUuid) -> Option<&'static str> {
+    Some(match uuid.as_u16()? {
+        0x2900 => "Characteristic Extended Properties",
+        0x2901 => "Characteristic User Description",
+        0x2902 => "Client Characteristic Configuration",
+        0x2903 => "Server Characteristic Configuration",
+        0x2904 => "Characteristic Presentation Format",
+        0x2905 => "Characteristic Aggregate Format",
+        0x2906 => "Valid Range",
+        0x2907 => "External Report Reference",
+        0x2908 => "Report Reference",
+        0x2909 => "Number of Digitals",
+        0x290a => "Value Trigger Setting",
+        0x290b => "Environmental Sensing Configuration",
+        0x290c => "Environmental Sensing Measurement",
+        0x290d => "Environmental Sensing Trigger Setting",
+        0x290e => "Time Trigger Setting",
+        0x290f => "Complete BR-EDR Transport Block Data",
+        0x2910 => "Observation Schedule",
+        0x2911 => "Valid Range and Accuracy",
+        _ => return None,
+    })
+}
+
+/// Returns the name of a company identifier, e.g. "Apple, Inc." for `0x004c`.
+pub fn company_name(id: u16) -> Option<&'static str> {
+    Some(match id {
+        0x0000 => "Ericsson AB",
+        0x0001 => "Nokia Mobile Phones",
+        0x0002 => "Intel Corp.",
+        0x0003 => "IBM Corp.",
+        0x0004 => "Toshiba Corp.",
+        0x0005 => "3Com",
+        0x0006 => "Microsoft",
+        0x0007 => "Lucent",
+        0x0008 => "Motorola",
+        0x0009 => "Infineon Technologies AG",
+        0x000a => "Qualcomm Technologies International, Ltd. (QTIL)",
+        0x000b => "Silicon Wave",
+        0x000c => "Digianswer A/S",
+        0x000d => "Texas Instruments Inc.",
+        0x000e => "Parthus Technologies Inc.",
+        0x000f => "Broadcom Corporation",
+        0x0010 => "Mitel Semiconductor",
+        0x0011 => "Widcomm, Inc.",
+        0x0012 => "Zeevo, Inc.",
+        0x0013 => "Atmel Corporation",
+        0x0014 => "Mitsubishi Electric Corporation",
+        0x0015 => "RTX A/S",
+        0x0016 => "KC Technology Inc.",
+        0x0017 => "Newlogic",
+        0x0018 => "Transilica, Inc.",
+        0x0019 => "Rohde & Schwarz GmbH & Co. KG",
+        0x001a => "TTPCom Limited",
+        0x001b => "Signia Technologies, Inc.",
+        0x001c => "Conexant Systems Inc.",
+        0x001d => "Qualcomm",
+        0x001e => "Inventel",
+        0x001f => "AVM Berlin",
+        0x0020 => "BandSpeed, Inc.",
+        0x0021 => "Mansella Ltd",
+        0x0022 => "NEC Corporation",
+        0x0023 => "WavePlus Technology Co., Ltd.",
+        0x0024 => "Alcatel",
+        0x0025 => "NXP B.V.",
+        0x0026 => "C Technologies",
+        0x0027 => "Open Interface",
+        0x0028 => "R F Micro Devices",
+        0x0029 => "Hitachi Ltd",
+        0x002a => "Symbol Technologies, Inc.",
+        0x002b => "Tenovis",
+        0x002c => "Macronix International Co. Ltd.",
+        0x002d => "GCT Semiconductor",
+        0x002e => "Norwood Systems",
+        0x002f => "MewTel Technology Inc.",
+        0x0030 => "ST Microelectronics",
+        0x0031 => "Synopsys, Inc.",
+        0x0032 => "Red-M (Communications) Ltd",
+        0x0033 => "Commil Ltd",
+        0x0034 => "Computer Access Technology Corporation (CATC)",
+        0x0035 => "Eclipse (HQ Espana) S.L.",
+        0x0036 => "Renesas Electronics Corporation",
+        0x0037 => "Mobilian Corporation",
+        0x0038 => "Syntronix Corporation",
+        0x0039 => "Integrated System Solution Corp.",
+        0x003a => "Panasonic Holdings Corporation",
+        0x003b => "Gennum Corporation",
+        0x003c => "BlackBerry Limited",
+        0x003d => "IPextreme, Inc.",
+        0x003e => "Systems and Chips, Inc",
+        0x003f => "Bluetooth SIG, Inc",
+        0x0040 => "Seiko Epson Corporation",
+        0x0041 => "Integrated Silicon Solution Taiwan, Inc.",
+        0x0042 => "CONWISE Technology Corporation Ltd",
+        0x0043 => "PARROT AUTOMOTIVE SAS",
+        0x0044 => "Socket Mobile",
+        0x0045 => "Atheros Communications, Inc.",
+        0x0046 => "MediaTek, Inc.",
+        0x0047 => "Bluegiga",
+        0x0048 => "Marvell Technology Group Ltd.",
+        0x0049 => "3DSP Corporation",
+        0x004a => "Accel Semiconductor Ltd.",
+        0x004b => "Continental Automotive Systems",
+        0x004c => "Apple, Inc.",
+        0x004d => "Staccato Communications, Inc.",
+        0x004e => "Avago Technologies",
+        0x004f => "APT Ltd.",
+        0x0050 => "SiRF Technology, Inc.",
+        0x0051 => "Tzero Technologies, Inc.",
+        0x0052 => "J&M Corporation",
+        0x0053 => "Free2move AB",
+        0x0054 => "3DiJoy Corporation",
+        0x0055 => "Plantronics, Inc.",
+        0x0056 => "Sony Ericsson Mobile Communications",
+        0x0057 => "Harman International Industries, Inc.",
+        0x0058 => "Vizio, Inc.",
+        0x0059 => "Nordic Semiconductor ASA",
+        0x005a => "EM Microelectronic-Marin SA",
+        0x005b => "Ralink Technology Corporation",
+        0x005c => "Belkin International, Inc.",
+        0x005d => "Realtek Semiconductor Corporation",
+        0x005e => "Stonestreet One, LLC",
+        0x005f => "Wicentric, Inc.",
+        0x0060 => "RivieraWaves S.A.S",
+        0x0061 => "RDA Microelectronics",
+        0x0062 => "Gibson Guitars",
+        0x0063 => "MiCommand Inc.",
+        0x0064 => "Band XI International, LLC",
+        0x0065 => "HP, Inc.",
+        0x0066 => "9Solutions Oy",
+        0x0067 => "GN Audio A/S",
+        0x0068 => "General Motors",
+        0x0069 => "A&D Engineering, Inc.",
+        0x006a => "LTIMINDTREE LIMITED",
+        0x006b => "Polar Electro OY",
+        0x006c => "Beautiful Enterprise Co., Ltd.",
+        0x006d => "BriarTek, Inc",
+        0x006e => "Summit Data Communications, Inc.",
+        0x006f => "Sound ID",
+        0x0070 => "Monster, LLC",
+        0x0071 => "connectBlue AB",
+        0x0072 => "ShangHai Super Smart Electronics Co. Ltd.",
+        0x0073 => "Group Sense Ltd.",
+        0x0074 => "Zomm, LLC",
+        0x0075 => "Samsung Electronics Co. Ltd.",
+        0x0076 => "Creative Technology Ltd.",
+        0x0077 => "Laird Connectivity LLC",
+        0x0078 => "Nike, Inc.",
+        0x0079 => "lesswire AG",
+        0x007a => "MStar Semiconductor, Inc.",
+        0x007b => "Hanlynn Technologies",
+        0x007c => "A & R Cambridge",
+        0x007d => "Seers Technology Co., Ltd.",
+        0x007e => "Sports Tracking Technologies Ltd.",
+        0x007f => "Autonet Mobile",
+        0x0080 => "DeLorme Publishing Company, Inc.",
+        0x0081 => "WuXi Vimicro",
+        0x0082 => "DSEA A/S",
+        0x0083 => "TimeKeeping Systems, Inc.",
+        0x0084 => "Ludus Helsinki Ltd.",
+        0x0085 => "BlueRadios, Inc.",
+        0x0086 => "Equinux AG",
+        0x0087 => "Garmin International, Inc.",
+        0x0088 => "Ecotest",
+        0x0089 => "GN Hearing A/S",
+        0x008a => "Jawbone",
+        0x008b => "Topcon Positioning Systems, LLC",
+        0x008c => "Gimbal Inc.",
+        0x008d => "Zscan Software",
+        0x008e => "Quintic Corp",
+        0x008f => "Telit Wireless Solutions GmbH",
+        0x0090 => "Funai Electric Co., Ltd.",
+        0x0091 => "Advanced PANMOBIL systems GmbH & Co. KG",
+        0x0092 => "ThinkOptics, Inc.",
+        0x0093 => "Universal Electronics, Inc.",
+        0x0094 => "Airoha Technology Corp.",
+        0x0095 => "NEC Lighting, Ltd.",
+        0x0096 => "ODM Technology, Inc.",
+        0x0097 => "ConnecteDevice Ltd.",
+        0x0098 => "zero1.tv GmbH",
+        0x0099 => "i.Tech Dynamic Global Distribution Ltd.",
+        0x009a => "Alpwise",
+        0x009b => "Jiangsu Toppower Automotive Electronics Co., Ltd.",
+        0x009c => "Colorfy, Inc.",
+        0x009d => "Geoforce Inc.",
+        0x009e => "Bose Corporation",
+        0x009f => "Suunto Oy",
+        0x00a0 => "Kensington Computer Products Group",
+        0x00a1 => "SR-Medizinelektronik",
+        0x00a2 => "Vertu Corporation Limited",
+        0x00a3 => "Meta Watch Ltd.",
+        0x00a4 => "LINAK A/S",
+        0x00a5 => "OTL Dynamics LLC",
+        0x00a6 => "Panda Ocean Inc.",
+        0x00a7 => "Visteon Corporation",
+        0x00a8 => "ARP Devices Limited",
+        0x00a9 => "MARELLI EUROPE S.P.A.",
+        0x00aa => "CAEN RFID srl",
+        0x00ab => "Ingenieur-Systemgruppe Zahn GmbH",
+        0x00ac => "Green Throttle Games",
+        0x00ad => "Peter Systemtechnik GmbH",
+        0x00ae => "Omegawave Oy",
+        0x00af => "Cinetix",
+        0x00b0 => "Passif Semiconductor Corp",
+        0x00b1 => "Saris Cycling Group, Inc",
+        0x00b2 => "Bekey A/S",
+        0x00b3 => "Clarinox Technologies Pty. Ltd.",
+        0x00b4 => "BDE Technology Co., Ltd.",
+        0x00b5 => "Swirl Networks",
+        0x00b6 => "Meso international",
+        0x00b7 => "TreLab Ltd",
+        0x00b8 => "Qualcomm Innovation Center, Inc. (QuIC)",
+        0x00b9 => "Johnson Controls, Inc.",
+        0x00ba => "Starkey Hearing Technologies",
+        0x00bb => "S-Power Electronics Limited",
+        0x00bc => "Ace Sensor Inc",
+        0x00bd => "Aplix Corporation",
+        0x00be => "AAMP of America",
+        0x00bf => "Stalmart Technology Limited",
+        0x00c0 => "AMICCOM Electronics Corporation",
+        0x00c1 => "Shenzhen Excelsecu Data Technology Co.,Ltd",
+        0x00c2 => "Geneq Inc.",
+        0x00c3 => "adidas AG",
+        0x00c4 => "LG Electronics",
+        0x00c5 => "Onset Computer Corporation",
+        0x00c6 => "Selfly BV",
+        0x00c7 => "Quuppa Oy.",
+        0x00c8 => "GeLo Inc",
+        0x00c9 => "Evluma",
+        0x00ca => "MC10",
+        0x00cb => "Binauric SE",
+        0x00cc => "Beats Electronics",
+        0x00cd => "Microchip Technology Inc.",
+        0x00ce => "Eve Systems GmbH",
+        0x00cf => "ARCHOS SA",
+        0x00d0 => "Dexcom, Inc.",
+        0x00d1 => "Polar Electro Europe B.V.",
+        0x00d2 => "Dialog Semiconductor B.V.",
+        0x00d3 => "Taixingbang Technology (HK) Co,. LTD.",
+        0x00d4 => "Kawantech",
+        0x00d5 => "Austco Communication Systems",
+        0x00d6 => "Timex Group USA, Inc.",
+        0x00d7 => "Qualcomm Technologies, Inc.",
+        0x00d8 => "Qualcomm Connected Experiences, Inc.",
+        0x00d9 => "Voyetra Turtle Beach",
+        0x00da => "txtr GmbH",
+        0x00db => "Snuza (Pty) Ltd",
+        0x00dc => "Procter & Gamble",
+        0x00dd => "Hosiden Corporation",
+        0x00de => "Muzik LLC",
+        0x00df => "Misfit Wearables Corp",
+        0x00e0 => "Google",
+        0x0118 => "Radius Networks, Inc.",
+        0x0131 => "Cypress Semiconductor",
+        0x0157 => "Anhui Huami Information Technology Co., Ltd.",
+        0x0171 => "Amazon.com Services LLC",
+        0x01da => "Logitech International SA",
+        0x027d => "HUAWEI Technologies Co., Ltd.",
+        0x02e5 => "Espressif Systems (Shanghai) Co., Ltd.",
+        0x038f => "Xiaomi Inc.",
+        0x0499 => "Ruuvi Innovations Ltd.",
+        _ => return None,
+    })
+}
+
+/// Returns the name of an appearance value, e.g. "Keyboard" for `0x03c1`.
+///
+/// Falls back to the category name when the subcategory is not assigned.
+pub fn appearance_name(appearance: u16) -> Option<&'static str> {
+    let category = appearance >> 6;
+    let subcategory = appearance & 0x3f;
+    appearance_subcategory_name(category, subcategory).or_else(|| {
+        Some(match category {
+            0x000 => "Unknown",
+            0x001 => "Phone",
+            0x002 => "Computer",
+            0x003 => "Watch",
+            0x004 => "Clock",
+            0x005 => "Display",
+            0x006 => "Remote Control",
+            0x007 => "Eye-glasses",
+            0x008 => "Tag",
+            0x009 => "Keyring",
+            0x00a => "Media Player",
+            0x00b => "Barcode Scanner",
+            0x00c => "Thermometer",
+            0x00d => "Heart Rate Sensor",
+            0x00e => "Blood Pressure",
+            0x00f => "Human Interface Device",
+            0x010 => "Glucose Meter",
+            0x011 => "Running Walking Sensor",
+            0x012 => "Cycling",
+            0x013 => "Control Device",
+            0x014 => "Network Device",
+            0x015 => "Sensor",
+            0x016 => "Light Fixtures",
+            0x017 => "Fan",
+            0x018 => "HVAC",
+            0x019 => "Air Conditioning",
+            0x01a => "Humidifier",
+            0x01b => "Heating",
+            0x01c => "Access Control",
+            0x01d => "Motorized Device",
+            0x01e => "Power Device",
+            0x01f => "Light Source",
+            0x020 => "Window Covering",
+            0x021 => "Audio Sink",
+            0x022 => "Audio Source",
+            0x023 => "Motorized Vehicle",
+            0x024 => "Domestic Appliance",
+            0x025 => "Wearable Audio Device",
+            0x026 => "Aircraft",
+            0x027 => "AV Equipment",
+            0x028 => "Display Equipment",
+            0x029 => "Hearing aid",
+            0x02a => "Gaming",
+            0x02b => "Signage",
+            0x031 => "Pulse Oximeter",
+            0x032 => "Weight Scale",
+            0x033 => "Personal Mobility Device",
+            0x034 => "Continuous Glucose Monitor",
+            0x035 => "Insulin Pump",
+            0x036 => "Medication Delivery",
+            0x037 => "Spirometer",
+            0x051 => "Outdoor Sports Activity",
+            _ => return None,
+        })
+    })
+}
+
+/// Returns the name of an appearance subcategory.
+fn appearance_subcategory_name(category: u16, subcategory: u16) -> Option<&'static str> {
+    Some(match (category, subcategory) {
+        (0x002, 0x01) => "Desktop Workstation",
+        (0x002, 0x02) => "Server-class Computer",
+        (0x002, 0x03) => "Laptop",
+        (0x002, 0x04) => "Handheld PC/PDA (clamshell)",
+        (0x002, 0x05) => "Palm-size PC/PDA",
+        (0x002, 0x06) => "Wearable computer (watch size)",
+        (0x002, 0x07) => "Tablet",
+        (0x002, 0x08) => "Docking Station",
+        (0x002, 0x09) => "All in One",
+        (0x002, 0x0a) => "Blade Server",
+        (0x002, 0x0b) => "Convertible",
+        (0x002, 0x0c) => "Detachable",
+        (0x002, 0x0d) => "IoT Gateway",
+        (0x002, 0x0e) => "Mini PC",
+        (0x002, 0x0f) => "Stick PC",
+        (0x003, 0x01) => "Sports Watch",
+        (0x003, 0x02) => "Smartwatch",
+        (0x00c, 0x01) => "Ear Thermometer",
+        (0x00d, 0x01) => "Heart Rate Belt",
+        (0x00e, 0x01) => "Arm Blood Pressure",
+        (0x00e, 0x02) => "Wrist Blood Pressure",
+        (0x00f, 0x01) => "Keyboard",
+        (0x00f, 0x02) => "Mouse",
+        (0x00f, 0x03) => "Joystick",
+        (0x00f, 0x04) => "Gamepad",
+        (0x00f, 0x05) => "Digitizer Tablet",
+        (0x00f, 0x06) => "Card Reader",
+        (0x00f, 0x07) => "Digital Pen",
+        (0x00f, 0x08) => "Barcode Scanner",
+        (0x00f, 0x09) => "Touchpad",
+        (0x00f, 0x0a) => "Presentation Remote",
+        (0x011, 0x01) => "In-Shoe Running Walking Sensor",
+        (0x011, 0x02) => "On-Shoe Running Walking Sensor",
+        (0x011, 0x03) => "On-Hip Running Walking Sensor",
+        (0x012, 0x01) => "Cycling Computer",
+        (0x012, 0x02) => "Speed Sensor",
+        (0x012, 0x03) => "Cadence Sensor",
+        (0x012, 0x04) => "Power Sensor",
+        (0x012, 0x05) => "Speed and Cadence Sensor",
+        (0x021, 0x01) => "Standalone Speaker",
+        (0x021, 0x02) => "Soundbar",
+        (0x021, 0x03) => "Bookshelf Speaker",
+        (0x021, 0x04) => "Standmounted Speaker",
+        (0x021, 0x05) => "Speakerphone",
+        (0x025, 0x01) => "Earbud",
+        (0x025, 0x02) => "Headset",
+        (0x025, 0x03) => "Headphones",
+        (0x025, 0x04) => "Neck Band",
+        (0x029, 0x01) => "In-ear hearing aid",
+        (0x029, 0x02) => "Behind-ear hearing aid",
+        (0x029, 0x03) => "Cochlear Implant",
+        (0x02a, 0x01) => "Home Video Game Console",
+        (0x02a, 0x02) => "Portable handheld console",
+        (0x031, 0x01) => "Fingertip Pulse Oximeter",
+        (0x031, 0x02) => "Wrist Worn Pulse Oximeter",
+        (0x051, 0x01) => "Location Display",
+        (0x051, 0x02) => "Location and Navigation Display",
+        (0x051, 0x03) => "Location Pod",
+        (0x051, 0x04) => "Location and Navigation Pod",
+        _ => return None,
+    })
+}
+
+/// Returns the name of a Class of Device major class, e.g. "Phone" for `0x02`.
+///
+/// # Arguments
+/// * `major` - The major device class, bits 8 to 12 of the Class of Device.
+pub fn major_class_name(major: u8) -> Option<&'static str> {
+    Some(match major {
+        0x00 => "Miscellaneous",
+        0x01 => "Computer",
+        0x02 => "Phone",
+        0x03 => "LAN/Network Access Point",
+        0x04 => "Audio/Video",
+        0x05 => "Peripheral",
+        0x06 => "Imaging",
+        0x07 => "Wearable",
+        0x08 => "Toy",
+        0x09 => "Health",
+        0x1f => "Uncategorized",
+        _ => return None,
+    })
+}
+
+/// Returns the name of a Class of Device minor class within `major`.
+///
+/// # Arguments
+/// * `major` - The major device class, bits 8 to 12 of the Class of Device.
+/// * `minor` - The minor device class, bits 2 to 7 of the Class of Device.
+pub fn minor_class_name(major: u8, minor: u8) -> Option<&'static str> {
+    Some(match (major, minor) {
+        (0x01, 0x00) => "Uncategorized",
+        (0x01, 0x01) => "Desktop workstation",
+        (0x01, 0x02) => "Server-class computer",
+        (0x01, 0x03) => "Laptop",
+        (0x01, 0x04) => "Handheld PC/PDA (clamshell)",
+        (0x01, 0x05) => "Palm-size PC/PDA",
+        (0x01, 0x06) => "Wearable computer (watch size)",
+        (0x01, 0x07) => "Tablet",
+        (0x02, 0x00) => "Uncategorized",
+        (0x02, 0x01) => "Cellular",
+        (0x02, 0x02) => "Cordless",
+        (0x02, 0x03) => "Smartphone",
+        (0x02, 0x04) => "Wired modem or voice gateway",
+        (0x02, 0x05) => "Common ISDN access",
+        (0x03, _) => match minor >> 3 {
+            0 => "Fully available",
+            1 => "1% to 17% utilized",
+            2 => "17% to 33% utilized",
+            3 => "33% to 50% utilized",
+            4 => "50% to 67% utilized",
+            5 => "67% to 83% utilized",
+            6 => "83% to 99% utilized",
+            _ => "No service available",
+        },
+        (0x04, 0x00) => "Uncategorized",
+        (0x04, 0x01) => "Wearable Headset Device",
+        (0x04, 0x02) => "Hands-free Device",
+        (0x04, 0x04) => "Microphone",
+        (0x04, 0x05) => "Loudspeaker",
+        (0x04, 0x06) => "Headphones",
+        (0x04, 0x07) => "Portable Audio",
+        (0x04, 0x08) => "Car audio",
+        (0x04, 0x09) => "Set-top box",
+        (0x04, 0x0a) => "HiFi Audio Device",
+        (0x04, 0x0b) => "VCR",
+        (0x04, 0x0c) => "Video Camera",
+        (0x04, 0x0d) => "Camcorder",
+        (0x04, 0x0e) => "Video Monitor",
+        (0x04, 0x0f) => "Video Display and Loudspeaker",
+        (0x04, 0x10) => "Video Conferencing",
+        (0x04, 0x12) => "Gaming/Toy",
+        (0x05, _) => match (minor & 0x0f, minor >> 4) {
+            (0x01, _) => "Joystick",
+            (0x02, _) => "Gamepad",
+            (0x03, _) => "Remote control",
+            (0x04, _) => "Sensing device",
+            (0x05, _) => "Digitizer tablet",
+            (0x06, _) => "Card Reader",
+            (0x07, _) => "Digital Pen",
+            (0x08, _) => "Handheld scanner",
+            (0x09, _) => "Handheld gestural input device",
+            (0x00, 0x01) => "Keyboard",
+            (0x00, 0x02) => "Pointing device",
+            (0x00, 0x03) => "Combo keyboard/pointing device",
+            (0x00, _) => "Uncategorized",
+            _ => return None,
+        },
+        (0x06, _) if minor & 0x20 != 0 => "Printer",
+        (0x06, _) if minor & 0x10 != 0 => "Scanner",
+        (0x06, _) if minor & 0x08 != 0 => "Camera",
+        (0x06, _) if minor & 0x04 != 0 => "Display",
+        (0x07, 0x01) => "Wristwatch",
+        (0x07, 0x02) => "Pager",
+        (0x07, 0x03) => "Jacket",
+        (0x07, 0x04) => "Helmet",
+        (0x07, 0x05) => "Glasses",
+        (0x07, 0x06) => "Pin",
+        (0x08, 0x01) => "Robot",
+        (0x08, 0x02) => "Vehicle",
+        (0x08, 0x03) => "Doll/Action figure",
+        (0x08, 0x04) => "Controller",
+        (0x08, 0x05) => "Game",
+        (0x09, 0x00) => "Undefined",
+        (0x09, 0x01) => "Blood Pressure Monitor",
+        (0x09, 0x02) => "Thermometer",
+        (0x09, 0x03) => "Weighing Scale",
+        (0x09, 0x04) => "Glucose Meter",
+        (0x09, 0x05) => "Pulse Oximeter",
+        (0x09, 0x06) => "Heart/Pulse Rate Monitor",
+        (0x09, 0x07) => "Health Data Display",
+        (0x09, 0x08) => "Step Counter",
+        (0x09, 0x09) => "Body Composition Analyzer",
+        (0x09, 0x0a) => "Peak Flow Monitor",
+        (0x09, 0x0b) => "Medication Monitor",
+        (0x09, 0x0c) => "Knee Prosthesis",
+        (0x09, 0x0d) => "Ankle Prosthesis",
+        (0x09, 0x0e) => "Generic Health Manager",
+        (0x09, 0x0f) => "Personal Mobility Device",
+        _ => return None,
+    })
+}
@@ -1,7 +1,108 @@
+use std::collections::HashMap;
+
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Str, Value};
+
 use crate::ObjectManagerProxy;
 
+/// A [`DeviceInfo`] field, named after the `org.bluez.Device1` property backing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceField {
+    Address,
+    Alias,
+    Connected,
+    Paired,
+    AddressType,
+    Connectable,
+    Discoverable,
+    DiscoverableTimeout,
+    Discovering,
+    Name,
+    Pairable,
+    PairableTimeout,
+    PowerState,
+    Powered,
+    Trusted,
+    Blocked,
+    ServicesResolved,
+    Rssi,
+    TxPower,
+    Class,
+    Appearance,
+    Icon,
+    Uuids,
+    ManufacturerData,
+    ServiceData,
+}
+
+impl DeviceField {
+    /// Returns the name of the D-Bus property backing the field.
+    pub fn property_name(&self) -> &'static str {
+        match self {
+            DeviceField::Address => "Address",
+            DeviceField::Alias => "Alias",
+            DeviceField::Connected => "Connected",
+            DeviceField::Paired => "Paired",
+            DeviceField::AddressType => "AddressType",
+            DeviceField::Connectable => "Connectable",
+            DeviceField::Discoverable => "Discoverable",
+            DeviceField::DiscoverableTimeout => "DiscoverableTimeout",
+            DeviceField::Discovering => "Discovering",
+            DeviceField::Name => "Name",
+            DeviceField::Pairable => "Pairable",
+            DeviceField::PairableTimeout => "PairableTimeout",
+            DeviceField::PowerState => "PowerState",
+            DeviceField::Powered => "Powered",
+            DeviceField::Trusted => "Trusted",
+            DeviceField::Blocked => "Blocked",
+            DeviceField::ServicesResolved => "ServicesResolved",
+            DeviceField::Rssi => "RSSI",
+            DeviceField::TxPower => "TxPower",
+            DeviceField::Class => "Class",
+            DeviceField::Appearance => "Appearance",
+            DeviceField::Icon => "Icon",
+            DeviceField::Uuids => "UUIDs",
+            DeviceField::ManufacturerData => "ManufacturerData",
+            DeviceField::ServiceData => "ServiceData",
+        }
+    }
+
+    /// Returns the field backed by the D-Bus property `name`, if any.
+    pub fn from_property(name: &str) -> Option<Self> {
+        match name {
+            "Address" => Some(DeviceField::Address),
+            "Alias" => Some(DeviceField::Alias),
+            "Connected" => Some(DeviceField::Connected),
+            "Paired" => Some(DeviceField::Paired),
+            "AddressType" => Some(DeviceField::AddressType),
+            "Connectable" => Some(DeviceField::Connectable),
+            "Discoverable" => Some(DeviceField::Discoverable),
+            "DiscoverableTimeout" => Some(DeviceField::DiscoverableTimeout),
+            "Discovering" => Some(DeviceField::Discovering),
+            "Name" => Some(DeviceField::Name),
+            "Pairable" => Some(DeviceField::Pairable),
+            "PairableTimeout" => Some(DeviceField::PairableTimeout),
+            "PowerState" => Some(DeviceField::PowerState),
+            "Powered" => Some(DeviceField::Powered),
+            "Trusted" => Some(DeviceField::Trusted),
+            "Blocked" => Some(DeviceField::Blocked),
+            "ServicesResolved" => Some(DeviceField::ServicesResolved),
+            "RSSI" => Some(DeviceField::Rssi),
+            "TxPower" => Some(DeviceField::TxPower),
+            "Class" => Some(DeviceField::Class),
+            "Appearance" => Some(DeviceField::Appearance),
+            "Icon" => Some(DeviceField::Icon),
+            "UUIDs" => Some(DeviceField::Uuids),
+            "ManufacturerData" => Some(DeviceField::ManufacturerData),
+            "ServiceData" => Some(DeviceField::ServiceData),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DeviceInfo {
+    /// Object path of the device, e.g. `/org/bluez/hci0/dev_00_11_22_33_44_55`.
+    pub path: Option<OwnedObjectPath>,
     pub address: String,
     pub alias: String,
     pub connected: bool,
@@ -17,129 +118,285 @@ pub struct DeviceInfo {
     pub power_state: Option<String>,
     pub powered: Option<bool>,
     pub trusted: Option<bool>,
+    pub blocked: Option<bool>,
+    pub services_resolved: Option<bool>,
     pub rssi: Option<i16>,
+    pub tx_power: Option<i16>,
+    /// Class of Device, only reported by BR/EDR devices.
+    pub class: Option<crate::ClassOfDevice>,
+    pub appearance: Option<u16>,
+    pub icon: Option<String>,
+    pub uuids: Vec<crate::BtUuid>,
+    /// Manufacturer specific data keyed by company identifier.
+    pub manufacturer_data: HashMap<u16, Vec<u8>>,
+    /// Service data keyed by service UUID.
+    pub service_data: HashMap<crate::BtUuid, Vec<u8>>,
+}
+
+impl DeviceInfo {
+    /// Builds a `DeviceInfo` for the device object at `path`.
+    ///
+    /// # Arguments
+    /// * `path` - The object path of the device.
+    /// * `properties` - The device's `org.bluez.Device1` property map.
+    pub fn from_object(
+        path: OwnedObjectPath,
+        properties: &HashMap<String, OwnedValue>,
+    ) -> crate::Result<Self> {
+        let mut device = Self::try_from(properties)?;
+        device.path = Some(path);
+        Ok(device)
+    }
+
+    /// Applies the changed properties of a `PropertiesChanged` signal.
+    ///
+    /// # Arguments
+    /// * `changed` - The changed `org.bluez.Device1` properties.
+    ///
+    /// # Returns
+    /// The fields whose value changed.
+    pub fn apply_changes(&mut self, changed: &HashMap<&str, Value<'_>>) -> Vec<DeviceField> {
+        changed
+            .iter()
+            .filter(|(name, value)| self.apply_property(name, value))
+            .filter_map(|(name, _)| DeviceField::from_property(name))
+            .collect()
+    }
+
+    /// Clears the invalidated properties of a `PropertiesChanged` signal.
+    ///
+    /// Optional fields are reset to `None`, collections are emptied and flags
+    /// are reset to `false`. The address is never cleared.
+    ///
+    /// # Arguments
+    /// * `invalidated` - The names of the invalidated `org.bluez.Device1` properties.
+    ///
+    /// # Returns
+    /// The fields whose value changed.
+    pub fn invalidate(&mut self, invalidated: &[&str]) -> Vec<DeviceField> {
+        invalidated
+            .iter()
+            .filter_map(|name| DeviceField::from_property(name))
+            .filter(|field| self.clear_field(*field))
+            .collect()
+    }
+
+    /// Resets `field` to its empty value, returning whether it changed.
+    fn clear_field(&mut self, field: DeviceField) -> bool {
+        match field {
+            DeviceField::Address => false,
+            DeviceField::Alias => {
+                let address = self.address.clone();
+                replace(&mut self.alias, address)
+            }
+            DeviceField::Connected => replace(&mut self.connected, false),
+            DeviceField::Paired => replace(&mut self.paired, false),
+            DeviceField::AddressType => replace(&mut self.address_type, None),
+            DeviceField::Connectable => replace(&mut self.connectable, None),
+            DeviceField::Discoverable => replace(&mut self.discoverable, None),
+            DeviceField::DiscoverableTimeout => replace(&mut self.discoverable_timeout, None),
+            DeviceField::Discovering => replace(&mut self.discovering, None),
+            DeviceField::Name => replace(&mut self.name, None),
+            DeviceField::Pairable => replace(&mut self.pairable, None),
+            DeviceField::PairableTimeout => replace(&mut self.pairable_timeout, None),
+            DeviceField::PowerState => replace(&mut self.power_state, None),
+            DeviceField::Powered => replace(&mut self.powered, None),
+            DeviceField::Trusted => replace(&mut self.trusted, None),
+            DeviceField::Blocked => replace(&mut self.blocked, None),
+            DeviceField::ServicesResolved => replace(&mut self.services_resolved, None),
+            DeviceField::Rssi => replace(&mut self.rssi, None),
+            DeviceField::TxPower => replace(&mut self.tx_power, None),
+            DeviceField::Class => replace(&mut self.class, None),
+            DeviceField::Appearance => replace(&mut self.appearance, None),
+            DeviceField::Icon => replace(&mut self.icon, None),
+            DeviceField::Uuids => replace(&mut self.uuids, Vec::new()),
+            DeviceField::ManufacturerData => replace(&mut self.manufacturer_data, HashMap::new()),
+            DeviceField::ServiceData => replace(&mut self.service_data, HashMap::new()),
+        }
+    }
+
+    /// Sets the field backing the property `name`, returning whether it changed.
+    fn apply_property(&mut self, name: &str, value: &Value<'_>) -> bool {
+        match name {
+            "Address" => match string(value) {
+                Some(address) => replace(&mut self.address, address),
+                None => false,
+            },
+            "Alias" => match string(value) {
+                Some(alias) => replace(&mut self.alias, alias),
+                None => false,
+            },
+            "Connected" => match value.downcast_ref::<bool>() {
+                Ok(connected) => replace(&mut self.connected, connected),
+                Err(_) => false,
+            },
+            "Paired" => match value.downcast_ref::<bool>() {
+                Ok(paired) => replace(&mut self.paired, paired),
+                Err(_) => false,
+            },
+            "AddressType" => update(&mut self.address_type, string(value)),
+            "Connectable" => update(&mut self.connectable, value.downcast_ref().ok()),
+            "Discoverable" => update(&mut self.discoverable, value.downcast_ref().ok()),
+            "DiscoverableTimeout" => {
+                update(&mut self.discoverable_timeout, value.downcast_ref().ok())
+            }
+            "Discovering" => update(&mut self.discovering, value.downcast_ref().ok()),
+            "Name" => update(&mut self.name, string(value)),
+            "Pairable" => update(&mut self.pairable, value.downcast_ref().ok()),
+            "PairableTimeout" => update(&mut self.pairable_timeout, value.downcast_ref().ok()),
+            "PowerState" => update(&mut self.power_state, string(value)),
+            "Powered" => update(&mut self.powered, value.downcast_ref().ok()),
+            "Trusted" => update(&mut self.trusted, value.downcast_ref().ok()),
+            "Blocked" => update(&mut self.blocked, value.downcast_ref().ok()),
+            "ServicesResolved" => update(&mut self.services_resolved, value.downcast_ref().ok()),
+            "RSSI" => update(&mut self.rssi, value.downcast_ref().ok()),
+            "TxPower" => update(&mut self.tx_power, value.downcast_ref().ok()),
+            "Class" => update(
+                &mut self.class,
+                value.downcast_ref::<u32>().ok().map(Into::into),
+            ),
+            "Appearance" => update(&mut self.appearance, value.downcast_ref().ok()),
+            "Icon" => update(&mut self.icon, string(value)),
+            "UUIDs" => convert(value).is_some_and(|uuids| replace(&mut self.uuids, uuids)),
+            "ManufacturerData" => {
+                convert(value).is_some_and(|data| replace(&mut self.manufacturer_data, data))
+            }
+            "ServiceData" => {
+                convert(value).is_some_and(|data| replace(&mut self.service_data, data))
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "assigned-numbers")]
+impl DeviceInfo {
+    /// Returns the assigned name of the device's appearance, e.g. "Keyboard".
+    pub fn appearance_name(&self) -> Option<&'static str> {
+        crate::assigned_numbers::appearance_name(self.appearance?)
+    }
+
+    /// Returns the names of the companies the device sent manufacturer data
+    /// for, ordered by company identifier, skipping unknown identifiers.
+    pub fn manufacturer_names(&self) -> Vec<&'static str> {
+        let mut ids: Vec<u16> = self.manufacturer_data.keys().copied().collect();
+        ids.sort_unstable();
+        ids.into_iter()
+            .filter_map(crate::assigned_numbers::company_name)
+            .collect()
+    }
+
+    /// Returns the names of the device's advertised services, skipping unknown UUIDs.
+    pub fn service_names(&self) -> Vec<&'static str> {
+        self.uuids.iter().filter_map(crate::BtUuid::name).collect()
+    }
+}
+
+impl TryFrom<&HashMap<String, OwnedValue>> for DeviceInfo {
+    type Error = crate::Error;
+
+    /// Builds a `DeviceInfo` from an `org.bluez.Device1` property map.
+    ///
+    /// The `path` field is left empty; use [`DeviceInfo::from_object`] when the
+    /// object path is known.
+    fn try_from(properties: &HashMap<String, OwnedValue>) -> crate::Result<Self> {
+        let address = properties
+            .get("Address")
+            .and_then(|address| string(address))
+            .ok_or(zbus::Error::MissingParameter("Address"))?;
+
+        let mut device = DeviceInfo {
+            path: None,
+            alias: address.clone(),
+            address,
+            connected: false,
+            paired: false,
+            address_type: None,
+            connectable: None,
+            discoverable: None,
+            discoverable_timeout: None,
+            discovering: None,
+            name: None,
+            pairable: None,
+            pairable_timeout: None,
+            power_state: None,
+            powered: None,
+            trusted: None,
+            blocked: None,
+            services_resolved: None,
+            rssi: None,
+            tx_power: None,
+            class: None,
+            appearance: None,
+            icon: None,
+            uuids: Vec::new(),
+            manufacturer_data: HashMap::new(),
+            service_data: HashMap::new(),
+        };
+
+        for (name, value) in properties {
+            device.apply_property(name, value);
+        }
+
+        Ok(device)
+    }
 }
 
-/// Lists all devices in the system.
-pub async fn list_devices() -> Vec<DeviceInfo> {
-    if let Ok (devices) = list_system_devices().await {
-        devices
-    } else {
-        Vec::new()
+/// Stores `value` in `field`, returning whether it differed.
+pub(crate) fn replace<T: PartialEq>(field: &mut T, value: T) -> bool {
+    if *field == value {
+        return false;
     }
+    *field = value;
+    true
 }
 
-pub async fn list_system_devices() -> Result<Vec<DeviceInfo>, Box<dyn std::error::Error + Send + Sync>> {
-    let conn = crate::get_system_connection().await?;
-    let proxy = ObjectManagerProxy::new(&conn).await?;
+/// Stores `value` in an optional field, returning whether it differed.
+///
+/// A `None` value, i.e. a property of the wrong type, leaves the field untouched.
+fn update<T: PartialEq>(field: &mut Option<T>, value: Option<T>) -> bool {
+    value.is_some_and(|value| replace(field, Some(value)))
+}
+
+/// Reads a string property.
+fn string(value: &Value<'_>) -> Option<String> {
+    value
+        .downcast_ref::<Str>()
+        .ok()
+        .map(|s| s.as_str().to_owned())
+}
+
+/// Converts a property, e.g. an array or a dictionary, into `T`.
+pub(crate) fn convert<T>(value: &Value<'_>) -> Option<T>
+where
+    T: TryFrom<OwnedValue>,
+{
+    value.try_to_owned().ok()?.try_into().ok()
+}
+
+/// Lists the devices known to BlueZ under an adapter.
+///
+/// # Arguments
+/// * `conn` - The system bus connection.
+/// * `adapter_path` - Object path of the adapter, e.g. [`crate::ADAPTER_PATH`].
+pub async fn list_devices(
+    conn: &zbus::Connection,
+    adapter_path: &str,
+) -> crate::Result<Vec<DeviceInfo>> {
+    let proxy = ObjectManagerProxy::new(conn).await?;
     let objects = proxy.get_managed_objects().await?;
 
     let mut devices = Vec::new();
-    let adapter_path = crate::get_adapter_path();
-    
+
     for (path, interface) in objects {
         if path.starts_with(&format!("{}/dev", adapter_path)) {
             if let Some(device) = interface.get("org.bluez.Device1") {
-                if let Some(address) = device.get("Address") {
-                    let addr = address
-                        .downcast_ref::<zbus::zvariant::Str>()
-                        .ok()
-                        .map(|s| s.as_str().to_owned())
-                        .unwrap_or_default()
-                        .to_string();
-
-                    let alias = device
-                        .get("Alias")
-                        .and_then(|alias| alias.downcast_ref::<zbus::zvariant::Str>().ok())
-                        .map(|s| s.as_str().to_owned())
-                        .unwrap_or(addr.to_string())
-                        .to_string();
-
-                    let connected = device
-                        .get("Connected")
-                        .and_then(|v| v.downcast_ref::<bool>().ok())
-                        .unwrap_or(false);
-
-                    let paired = device
-                        .get("Paired")
-                        .and_then(|v| v.downcast_ref::<bool>().ok())
-                        .unwrap_or(false);
-
-                    let address_type = device
-                        .get("AddressType")
-                        .and_then(|v| v.downcast_ref::<zbus::zvariant::Str>().ok())
-                        .map(|s| s.as_str().to_owned());
-
-                    let connectable = device
-                        .get("Connectable")
-                        .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                    let discoverable = device
-                        .get("Discoverable")
-                        .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                    let discoverable_timeout = device
-                        .get("DiscoverableTimeout")
-                        .and_then(|v| v.downcast_ref::<u32>().ok());
-
-                    let discovering = device
-                        .get("Discovering")
-                        .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                    let name = device
-                        .get("Name")
-                        .and_then(|v| v.downcast_ref::<zbus::zvariant::Str>().ok())
-                        .map(|s| s.as_str().to_owned());
-
-                    let pairable = device
-                        .get("Pairable")
-                        .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                    let pairable_timeout = device
-                        .get("PairableTimeout")
-                        .and_then(|v| v.downcast_ref::<u32>().ok());
-
-                    let power_state = device
-                        .get("PowerState")
-                        .and_then(|v| v.downcast_ref::<zbus::zvariant::Str>().ok())
-                        .map(|s| s.as_str().to_owned());
-
-                    let powered = device
-                        .get("Powered")
-                        .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                    let trusted = device
-                        .get("Trusted")
-                        .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                    let rssi = device
-                        .get("RSSI")
-                        .and_then(|v| v.downcast_ref::<i16>().ok());
-
-                    let device_info = DeviceInfo {
-                        address: addr,
-                        alias,
-                        connected,
-                        paired,
-                        address_type,
-                        connectable,
-                        discoverable,
-                        discoverable_timeout,
-                        discovering,
-                        name,
-                        pairable,
-                        pairable_timeout,
-                        power_state,
-                        powered,
-                        trusted,
-                        rssi,
-                    };
-
+                if let Ok(device_info) = DeviceInfo::from_object(path, device) {
                     devices.push(device_info);
                 }
             }
         }
     }
-    
+
     Ok(devices)
 }
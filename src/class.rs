@@ -0,0 +1,436 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use zbus::zvariant::{self, OwnedValue, Type, Value};
+
+/// A service class bit of a [`ClassOfDevice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServiceClass {
+    LimitedDiscoverableMode,
+    LeAudio,
+    Positioning,
+    Networking,
+    Rendering,
+    Capturing,
+    ObjectTransfer,
+    Audio,
+    Telephony,
+    Information,
+}
+
+impl ServiceClass {
+    /// Every service class, in bit order.
+    pub const ALL: [ServiceClass; 10] = [
+        ServiceClass::LimitedDiscoverableMode,
+        ServiceClass::LeAudio,
+        ServiceClass::Positioning,
+        ServiceClass::Networking,
+        ServiceClass::Rendering,
+        ServiceClass::Capturing,
+        ServiceClass::ObjectTransfer,
+        ServiceClass::Audio,
+        ServiceClass::Telephony,
+        ServiceClass::Information,
+    ];
+
+    /// Returns the bit of the service class within the Class of Device.
+    pub fn bit(&self) -> u32 {
+        match self {
+            ServiceClass::LimitedDiscoverableMode => 1 << 13,
+            ServiceClass::LeAudio => 1 << 14,
+            ServiceClass::Positioning => 1 << 16,
+            ServiceClass::Networking => 1 << 17,
+            ServiceClass::Rendering => 1 << 18,
+            ServiceClass::Capturing => 1 << 19,
+            ServiceClass::ObjectTransfer => 1 << 20,
+            ServiceClass::Audio => 1 << 21,
+            ServiceClass::Telephony => 1 << 22,
+            ServiceClass::Information => 1 << 23,
+        }
+    }
+}
+
+/// The major device class of a [`ClassOfDevice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MajorClass {
+    Miscellaneous,
+    Computer,
+    Phone,
+    NetworkAccessPoint,
+    AudioVideo,
+    Peripheral,
+    Imaging,
+    Wearable,
+    Toy,
+    Health,
+    Uncategorized,
+    /// A reserved major class value.
+    Reserved(u8),
+}
+
+impl From<u8> for MajorClass {
+    fn from(major: u8) -> Self {
+        match major {
+            0x00 => MajorClass::Miscellaneous,
+            0x01 => MajorClass::Computer,
+            0x02 => MajorClass::Phone,
+            0x03 => MajorClass::NetworkAccessPoint,
+            0x04 => MajorClass::AudioVideo,
+            0x05 => MajorClass::Peripheral,
+            0x06 => MajorClass::Imaging,
+            0x07 => MajorClass::Wearable,
+            0x08 => MajorClass::Toy,
+            0x09 => MajorClass::Health,
+            0x1f => MajorClass::Uncategorized,
+            major => MajorClass::Reserved(major),
+        }
+    }
+}
+
+/// The minor device class of a [`ClassOfDevice`], interpreted according to its major class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MinorClass {
+    Computer(ComputerClass),
+    Phone(PhoneClass),
+    /// Utilisation of a network access point, from 0 (fully available) to 7 (no service).
+    NetworkAccessPoint(u8),
+    AudioVideo(AudioVideoClass),
+    Peripheral {
+        keyboard: bool,
+        pointing: bool,
+        kind: PeripheralClass,
+    },
+    Imaging {
+        display: bool,
+        camera: bool,
+        scanner: bool,
+        printer: bool,
+    },
+    Wearable(WearableClass),
+    Toy(ToyClass),
+    Health(HealthClass),
+    /// The raw minor class of a major class without defined minor classes.
+    Other(u8),
+}
+
+/// Minor class of a computer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComputerClass {
+    Uncategorized,
+    Desktop,
+    Server,
+    Laptop,
+    HandheldPda,
+    PalmSizePda,
+    Wearable,
+    Tablet,
+    Reserved(u8),
+}
+
+/// Minor class of a phone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhoneClass {
+    Uncategorized,
+    Cellular,
+    Cordless,
+    Smartphone,
+    WiredModem,
+    CommonIsdnAccess,
+    Reserved(u8),
+}
+
+/// Minor class of an audio/video device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioVideoClass {
+    Uncategorized,
+    Headset,
+    HandsFree,
+    Microphone,
+    Loudspeaker,
+    Headphones,
+    PortableAudio,
+    CarAudio,
+    SetTopBox,
+    HifiAudio,
+    Vcr,
+    VideoCamera,
+    Camcorder,
+    VideoMonitor,
+    VideoDisplayAndLoudspeaker,
+    VideoConferencing,
+    GamingToy,
+    Reserved(u8),
+}
+
+/// Minor class of a peripheral, besides its keyboard and pointing bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PeripheralClass {
+    Uncategorized,
+    Joystick,
+    Gamepad,
+    RemoteControl,
+    SensingDevice,
+    DigitizerTablet,
+    CardReader,
+    DigitalPen,
+    HandheldScanner,
+    GesturalInput,
+    Reserved(u8),
+}
+
+/// Minor class of a wearable device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WearableClass {
+    Wristwatch,
+    Pager,
+    Jacket,
+    Helmet,
+    Glasses,
+    Pin,
+    Reserved(u8),
+}
+
+/// Minor class of a toy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToyClass {
+    Robot,
+    Vehicle,
+    Doll,
+    Controller,
+    Game,
+    Reserved(u8),
+}
+
+/// Minor class of a health device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HealthClass {
+    Undefined,
+    BloodPressureMonitor,
+    Thermometer,
+    WeighingScale,
+    GlucoseMeter,
+    PulseOximeter,
+    HeartRateMonitor,
+    HealthDataDisplay,
+    StepCounter,
+    BodyCompositionAnalyzer,
+    PeakFlowMonitor,
+    MedicationMonitor,
+    KneeProsthesis,
+    AnkleProsthesis,
+    GenericHealthManager,
+    PersonalMobilityDevice,
+    Reserved(u8),
+}
+
+/// The 24-bit Class of Device advertised by BR/EDR devices in the `Class` property.
+///
+/// Bits 13 to 23 hold the [`ServiceClass`]es, bits 8 to 12 the [`MajorClass`]
+/// and bits 2 to 7 the [`MinorClass`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ClassOfDevice(u32);
+
+impl ClassOfDevice {
+    /// Wraps a raw Class of Device; bits above 23 are ignored.
+    pub const fn from_u32(class: u32) -> Self {
+        Self(class & 0x00ff_ffff)
+    }
+
+    /// Returns the raw 24-bit value.
+    pub const fn as_u32(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns whether the device announces `service`.
+    pub fn has_service(&self, service: ServiceClass) -> bool {
+        self.0 & service.bit() != 0
+    }
+
+    /// Returns the service classes the device announces.
+    pub fn service_classes(&self) -> Vec<ServiceClass> {
+        ServiceClass::ALL
+            .into_iter()
+            .filter(|service| self.has_service(*service))
+            .collect()
+    }
+
+    /// Returns the raw 5-bit major class.
+    pub fn major_bits(&self) -> u8 {
+        ((self.0 >> 8) & 0x1f) as u8
+    }
+
+    /// Returns the raw 6-bit minor class.
+    pub fn minor_bits(&self) -> u8 {
+        ((self.0 >> 2) & 0x3f) as u8
+    }
+
+    /// Returns the major device class.
+    pub fn major(&self) -> MajorClass {
+        MajorClass::from(self.major_bits())
+    }
+
+    /// Returns the minor device class, interpreted according to the major class.
+    pub fn minor(&self) -> MinorClass {
+        let minor = self.minor_bits();
+        match self.major() {
+            MajorClass::Computer => MinorClass::Computer(match minor {
+                0x00 => ComputerClass::Uncategorized,
+                0x01 => ComputerClass::Desktop,
+                0x02 => ComputerClass::Server,
+                0x03 => ComputerClass::Laptop,
+                0x04 => ComputerClass::HandheldPda,
+                0x05 => ComputerClass::PalmSizePda,
+                0x06 => ComputerClass::Wearable,
+                0x07 => ComputerClass::Tablet,
+                minor => ComputerClass::Reserved(minor),
+            }),
+            MajorClass::Phone => MinorClass::Phone(match minor {
+                0x00 => PhoneClass::Uncategorized,
+                0x01 => PhoneClass::Cellular,
+                0x02 => PhoneClass::Cordless,
+                0x03 => PhoneClass::Smartphone,
+                0x04 => PhoneClass::WiredModem,
+                0x05 => PhoneClass::CommonIsdnAccess,
+                minor => PhoneClass::Reserved(minor),
+            }),
+            MajorClass::NetworkAccessPoint => MinorClass::NetworkAccessPoint(minor >> 3),
+            MajorClass::AudioVideo => MinorClass::AudioVideo(match minor {
+                0x00 => AudioVideoClass::Uncategorized,
+                0x01 => AudioVideoClass::Headset,
+                0x02 => AudioVideoClass::HandsFree,
+                0x04 => AudioVideoClass::Microphone,
+                0x05 => AudioVideoClass::Loudspeaker,
+                0x06 => AudioVideoClass::Headphones,
+                0x07 => AudioVideoClass::PortableAudio,
+                0x08 => AudioVideoClass::CarAudio,
+                0x09 => AudioVideoClass::SetTopBox,
+                0x0a => AudioVideoClass::HifiAudio,
+                0x0b => AudioVideoClass::Vcr,
+                0x0c => AudioVideoClass::VideoCamera,
+                0x0d => AudioVideoClass::Camcorder,
+                0x0e => AudioVideoClass::VideoMonitor,
+                0x0f => AudioVideoClass::VideoDisplayAndLoudspeaker,
+                0x10 => AudioVideoClass::VideoConferencing,
+                0x12 => AudioVideoClass::GamingToy,
+                minor => AudioVideoClass::Reserved(minor),
+            }),
+            MajorClass::Peripheral => MinorClass::Peripheral {
+                keyboard: minor & 0x10 != 0,
+                pointing: minor & 0x20 != 0,
+                kind: match minor & 0x0f {
+                    0x00 => PeripheralClass::Uncategorized,
+                    0x01 => PeripheralClass::Joystick,
+                    0x02 => PeripheralClass::Gamepad,
+                    0x03 => PeripheralClass::RemoteControl,
+                    0x04 => PeripheralClass::SensingDevice,
+                    0x05 => PeripheralClass::DigitizerTablet,
+                    0x06 => PeripheralClass::CardReader,
+                    0x07 => PeripheralClass::DigitalPen,
+                    0x08 => PeripheralClass::HandheldScanner,
+                    0x09 => PeripheralClass::GesturalInput,
+                    kind => PeripheralClass::Reserved(kind),
+                },
+            },
+            MajorClass::Imaging => MinorClass::Imaging {
+                display: minor & 0x04 != 0,
+                camera: minor & 0x08 != 0,
+                scanner: minor & 0x10 != 0,
+                printer: minor & 0x20 != 0,
+            },
+            MajorClass::Wearable => MinorClass::Wearable(match minor {
+                0x01 => WearableClass::Wristwatch,
+                0x02 => WearableClass::Pager,
+                0x03 => WearableClass::Jacket,
+                0x04 => WearableClass::Helmet,
+                0x05 => WearableClass::Glasses,
+                0x06 => WearableClass::Pin,
+                minor => WearableClass::Reserved(minor),
+            }),
+            MajorClass::Toy => MinorClass::Toy(match minor {
+                0x01 => ToyClass::Robot,
+                0x02 => ToyClass::Vehicle,
+                0x03 => ToyClass::Doll,
+                0x04 => ToyClass::Controller,
+                0x05 => ToyClass::Game,
+                minor => ToyClass::Reserved(minor),
+            }),
+            MajorClass::Health => MinorClass::Health(match minor {
+                0x00 => HealthClass::Undefined,
+                0x01 => HealthClass::BloodPressureMonitor,
+                0x02 => HealthClass::Thermometer,
+                0x03 => HealthClass::WeighingScale,
+                0x04 => HealthClass::GlucoseMeter,
+                0x05 => HealthClass::PulseOximeter,
+                0x06 => HealthClass::HeartRateMonitor,
+                0x07 => HealthClass::HealthDataDisplay,
+                0x08 => HealthClass::StepCounter,
+                0x09 => HealthClass::BodyCompositionAnalyzer,
+                0x0a => HealthClass::PeakFlowMonitor,
+                0x0b => HealthClass::MedicationMonitor,
+                0x0c => HealthClass::KneeProsthesis,
+                0x0d => HealthClass::AnkleProsthesis,
+                0x0e => HealthClass::GenericHealthManager,
+                0x0f => HealthClass::PersonalMobilityDevice,
+                minor => HealthClass::Reserved(minor),
+            }),
+            _ => MinorClass::Other(minor),
+        }
+    }
+
+    /// Returns the assigned name of the major class, e.g. "Phone".
+    #[cfg(feature = "assigned-numbers")]
+    pub fn major_name(&self) -> Option<&'static str> {
+        crate::assigned_numbers::major_class_name(self.major_bits())
+    }
+
+    /// Returns the assigned name of the minor class, e.g. "Smartphone".
+    #[cfg(feature = "assigned-numbers")]
+    pub fn minor_name(&self) -> Option<&'static str> {
+        crate::assigned_numbers::minor_class_name(self.major_bits(), self.minor_bits())
+    }
+}
+
+impl fmt::Debug for ClassOfDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ClassOfDevice({:#08x})", self.0)
+    }
+}
+
+impl From<u32> for ClassOfDevice {
+    fn from(class: u32) -> Self {
+        Self::from_u32(class)
+    }
+}
+
+impl From<ClassOfDevice> for u32 {
+    fn from(class: ClassOfDevice) -> Self {
+        class.0
+    }
+}
+
+impl Type for ClassOfDevice {
+    const SIGNATURE: &'static zvariant::Signature = u32::SIGNATURE;
+}
+
+impl TryFrom<Value<'_>> for ClassOfDevice {
+    type Error = zvariant::Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        u32::try_from(value).map(Self::from_u32)
+    }
+}
+
+impl TryFrom<OwnedValue> for ClassOfDevice {
+    type Error = zvariant::Error;
+
+    fn try_from(value: OwnedValue) -> Result<Self, Self::Error> {
+        u32::try_from(value).map(Self::from_u32)
+    }
+}
+
+impl From<ClassOfDevice> for Value<'static> {
+    fn from(class: ClassOfDevice) -> Self {
+        Value::U32(class.0)
+    }
+}
@@ -6,8 +6,8 @@
 /// # Returns
 /// * `Connection` - An established connection to the system bus.
 ///
-/// # Panics
-/// This function will panic if the connection cannot be established.
-pub async fn get_system_connection() -> Result<zbus::Connection, zbus::Error> {
-    zbus::Connection::system().await
+/// # Errors
+/// Returns an error if the connection cannot be established.
+pub async fn get_system_connection() -> crate::Result<zbus::Connection> {
+    Ok(zbus::Connection::system().await?)
 }
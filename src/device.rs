@@ -1,58 +1,171 @@
+use std::collections::HashMap;
+
+use zbus::zvariant::{OwnedObjectPath, OwnedValue};
+
 /// Defines the `Device` trait for interfacing with Bluetooth devices via D-Bus.
 /// Provides methods for connecting, pairing, and managing device properties.
 #[zbus::proxy(default_service = "org.bluez", interface = "org.bluez.Device1")]
 pub trait Device {
     /// Initiates a connection to the Bluetooth device.
-    fn connect(&self) -> zbus::Result<()>;
+    fn connect(&self) -> crate::Result<()>;
 
     /// Disconnects the Bluetooth device.
-    fn disconnect(&self) -> zbus::Result<()>;
+    fn disconnect(&self) -> crate::Result<()>;
 
     /// Pairs the Bluetooth device with the adapter.
-    fn pair(&self) -> zbus::Result<()>;
+    fn pair(&self) -> crate::Result<()>;
 
     /// Cancels an ongoing pairing process.
-    fn cancel_pairing(&self) -> zbus::Result<()>;
+    fn cancel_pairing(&self) -> crate::Result<()>;
+
+    /// Connects a specific profile of the device.
+    ///
+    /// # Arguments
+    /// * `uuid` - The UUID of the profile to connect.
+    fn connect_profile(&self, uuid: &crate::BtUuid) -> crate::Result<()>;
+
+    /// Disconnects a specific profile of the device.
+    ///
+    /// # Arguments
+    /// * `uuid` - The UUID of the profile to disconnect.
+    fn disconnect_profile(&self, uuid: &crate::BtUuid) -> crate::Result<()>;
 
     /// Checks if the device's services have been resolved.
     #[zbus(property)]
-    fn services_resolved(&self) -> zbus::Result<bool>;
+    fn services_resolved(&self) -> crate::Result<bool>;
 
     /// Retrieves the Bluetooth device's unique address (MAC address).
     #[zbus(property)]
-    fn address(&self) -> zbus::Result<String>;
+    fn address(&self) -> crate::Result<String>;
 
     /// Retrieves the name of the Bluetooth device.
     #[zbus(property)]
-    fn name(&self) -> zbus::Result<String>;
+    fn name(&self) -> crate::Result<String>;
 
     /// Checks if the device is currently connected.
     #[zbus(property)]
-    fn connected(&self) -> zbus::Result<bool>;
+    fn connected(&self) -> crate::Result<bool>;
 
     /// Checks if the device is paired.
     #[zbus(property)]
-    fn paired(&self) -> zbus::Result<bool>;
+    fn paired(&self) -> crate::Result<bool>;
 
     /// Checks if the device is marked as trusted.
     #[zbus(property)]
-    fn trusted(&self) -> zbus::Result<bool>;
+    fn trusted(&self) -> crate::Result<bool>;
 
     /// Sets the device as trusted or untrusted.
     ///
     /// # Arguments
     /// * `trusted` - `true` to mark as trusted, `false` to unmark.
     #[zbus(property)]
-    fn set_trusted(&self, trusted: bool) -> zbus::Result<()>;
+    fn set_trusted(&self, trusted: bool) -> crate::Result<()>;
 
     /// Checks if the device is blocked.
     #[zbus(property)]
-    fn blocked(&self) -> zbus::Result<bool>;
+    fn blocked(&self) -> crate::Result<bool>;
 
     /// Blocks or unblocks the device.
     ///
     /// # Arguments
     /// * `blocked` - `true` to block, `false` to unblock.
     #[zbus(property)]
-    fn set_blocked(&self, blocked: bool) -> zbus::Result<()>;
+    fn set_blocked(&self, blocked: bool) -> crate::Result<()>;
+
+    /// Retrieves the type of the device's address, `"public"` or `"random"`.
+    #[zbus(property)]
+    fn address_type(&self) -> crate::Result<String>;
+
+    /// Retrieves the alias of the device, falling back to its name or address.
+    #[zbus(property)]
+    fn alias(&self) -> crate::Result<String>;
+
+    /// Sets a local alias for the device; an empty string restores the default.
+    ///
+    /// # Arguments
+    /// * `alias` - The new alias to set.
+    #[zbus(property)]
+    fn set_alias(&self, alias: &str) -> crate::Result<()>;
+
+    /// Retrieves the Bluetooth class of device (BR/EDR only).
+    #[zbus(property)]
+    fn class(&self) -> crate::Result<crate::ClassOfDevice>;
+
+    /// Retrieves the external appearance of the device (LE only).
+    #[zbus(property)]
+    fn appearance(&self) -> crate::Result<u16>;
+
+    /// Retrieves the freedesktop.org icon name proposed for the device.
+    #[zbus(property)]
+    fn icon(&self) -> crate::Result<String>;
+
+    /// Retrieves the UUIDs of the services offered by the device.
+    #[zbus(property, name = "UUIDs")]
+    fn uuids(&self) -> crate::Result<Vec<crate::BtUuid>>;
+
+    /// Retrieves the signal strength seen during the last inquiry or advertisement, in dBm.
+    #[zbus(property, name = "RSSI")]
+    fn rssi(&self) -> crate::Result<i16>;
+
+    /// Retrieves the advertised transmit power level, in dBm.
+    #[zbus(property)]
+    fn tx_power(&self) -> crate::Result<i16>;
+
+    /// Retrieves the advertised manufacturer specific data, keyed by company identifier.
+    #[zbus(property)]
+    fn manufacturer_data(&self) -> crate::Result<HashMap<u16, Vec<u8>>>;
+
+    /// Retrieves the advertised service data, keyed by service UUID.
+    #[zbus(property)]
+    fn service_data(&self) -> crate::Result<HashMap<crate::BtUuid, Vec<u8>>>;
+
+    /// Retrieves the advertising flags of the remote device.
+    #[zbus(property)]
+    fn advertising_flags(&self) -> crate::Result<Vec<u8>>;
+
+    /// Retrieves the raw advertising data, keyed by AD type.
+    #[zbus(property)]
+    fn advertising_data(&self) -> crate::Result<HashMap<u8, Vec<u8>>>;
+
+    /// Checks if the device only supports the pre-2.1 pairing mechanism.
+    #[zbus(property)]
+    fn legacy_pairing(&self) -> crate::Result<bool>;
+
+    /// Retrieves the remote device ID information in modalias format.
+    #[zbus(property)]
+    fn modalias(&self) -> crate::Result<String>;
+
+    /// Retrieves the object path of the adapter the device belongs to.
+    #[zbus(property)]
+    fn adapter(&self) -> crate::Result<OwnedObjectPath>;
+
+    /// Checks if the device is allowed to wake up the host from system suspend.
+    #[zbus(property)]
+    fn wake_allowed(&self) -> crate::Result<bool>;
+
+    /// Allows or forbids the device to wake up the host.
+    ///
+    /// # Arguments
+    /// * `wake_allowed` - `true` to allow wake up.
+    #[zbus(property)]
+    fn set_wake_allowed(&self, wake_allowed: bool) -> crate::Result<()>;
+
+    /// Retrieves the coordinated sets the device is a member of, keyed by set object path.
+    #[zbus(property)]
+    fn sets(&self) -> crate::Result<HashMap<OwnedObjectPath, HashMap<String, OwnedValue>>>;
+
+    /// Checks if the pairing with the device is stored as a bond.
+    #[zbus(property)]
+    fn bonded(&self) -> crate::Result<bool>;
+
+    /// Retrieves the bearer preferred for connections, e.g. `"last-used"`, `"le"` or `"bredr"`.
+    #[zbus(property)]
+    fn preferred_bearer(&self) -> crate::Result<String>;
+
+    /// Sets the bearer preferred for connections.
+    ///
+    /// # Arguments
+    /// * `bearer` - `"last-used"`, `"le"`, `"bredr"` or `"last-seen"`.
+    #[zbus(property)]
+    fn set_preferred_bearer(&self, bearer: &str) -> crate::Result<()>;
 }
@@ -0,0 +1,75 @@
+/// Errors returned by bluebus.
+///
+/// BlueZ reports failures as `org.bluez.Error.*` D-Bus errors; they are decoded
+/// into the matching variant, carrying the human-readable message sent along
+/// with them. Anything else is kept as [`Error::ZBus`].
+#[derive(Debug, zbus::DBusError)]
+#[zbus(prefix = "org.bluez.Error")]
+pub enum Error {
+    /// A D-Bus or transport error that is not a BlueZ error.
+    #[zbus(error)]
+    ZBus(zbus::Error),
+    /// The adapter is not ready, usually because it is powered off.
+    NotReady(String),
+    /// The arguments passed to BlueZ were rejected.
+    InvalidArguments(String),
+    /// The same operation is already in progress.
+    InProgress(String),
+    /// The device is already connected.
+    AlreadyConnected(String),
+    /// The object, e.g. a paired device or a registered agent, already exists.
+    AlreadyExists(String),
+    /// The device is not connected.
+    NotConnected(String),
+    /// Pairing failed because authentication did not succeed.
+    AuthenticationFailed(String),
+    /// Pairing was canceled by the agent or the remote device.
+    AuthenticationCanceled(String),
+    /// Pairing was rejected by the agent or the remote device.
+    AuthenticationRejected(String),
+    /// The remote device did not answer the pairing request in time.
+    AuthenticationTimeout(String),
+    /// Establishing the connection failed.
+    ConnectionAttemptFailed(String),
+    /// The operation is not permitted, e.g. reading a write-only characteristic.
+    NotPermitted(String),
+    /// The caller is not authorized to perform the operation.
+    NotAuthorized(String),
+    /// The operation is not available in the current state.
+    NotAvailable(String),
+    /// The operation is not supported by the adapter, device or BlueZ version.
+    NotSupported(String),
+    /// The requested object does not exist.
+    DoesNotExist(String),
+    /// The request was rejected.
+    Rejected(String),
+    /// The request was canceled.
+    Canceled(String),
+    /// A GATT read or write used an invalid offset.
+    InvalidOffset(String),
+    /// A GATT write used a value of invalid length.
+    InvalidValueLength(String),
+    /// The operation failed for another reason.
+    Failed(String),
+}
+
+/// Result type used throughout bluebus.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl From<zbus::fdo::Error> for Error {
+    fn from(error: zbus::fdo::Error) -> Self {
+        Error::from(zbus::Error::from(error))
+    }
+}
+
+impl From<zbus::zvariant::Error> for Error {
+    fn from(error: zbus::zvariant::Error) -> Self {
+        Error::from(zbus::Error::from(error))
+    }
+}
+
+impl From<zbus::names::Error> for Error {
+    fn from(error: zbus::names::Error) -> Self {
+        Error::from(zbus::Error::from(error))
+    }
+}
@@ -1,27 +1,29 @@
 use zbus::object_server::Interface;
 use zbus::zvariant::Optional;
-use zbus::{fdo, interface, proxy, Connection};
+use zbus::{interface, proxy, Connection};
 
-#[proxy(name = "org.bluez.GattService1")]
+use crate::BtUuid;
+
+#[proxy(default_service = "org.bluez", interface = "org.bluez.GattService1")]
 pub trait GattService1 {
-    #[zbus(property)]
-    fn uuid(&self) -> fdo::Result<String>;
+    #[zbus(property, name = "UUID")]
+    fn uuid(&self) -> crate::Result<BtUuid>;
 
     #[zbus(property)]
-    fn primary(&self) -> fdo::Result<bool>;
+    fn primary(&self) -> crate::Result<bool>;
 
     #[zbus(property)]
-    fn device(&self) -> fdo::Result<Optional<String>>;
+    fn device(&self) -> crate::Result<Optional<String>>;
 
     #[zbus(property)]
-    fn includes(&self) -> fdo::Result<Vec<String>>;
+    fn includes(&self) -> crate::Result<Vec<String>>;
 
     #[zbus(property)]
-    fn handle(&self) -> fdo::Result<Optional<u16>>;
+    fn handle(&self) -> crate::Result<Optional<u16>>;
 }
 
 pub struct GattService {
-    pub uuid: String,
+    pub uuid: BtUuid,
     pub primary: bool,
     pub device: Option<String>,
     pub includes: Vec<String>,
@@ -30,74 +32,103 @@ pub struct GattService {
 
 #[interface(name = "org.bluez.GattService1")]
 impl GattService {
-    fn uuid(&self) -> fdo::Result<String> {
-        Ok(self.uuid.clone())
+    fn uuid(&self) -> crate::Result<BtUuid> {
+        Ok(self.uuid)
     }
 
-    fn primary(&self) -> fdo::Result<bool> {
+    fn primary(&self) -> crate::Result<bool> {
         Ok(self.primary)
     }
 
-    fn device(&self) -> fdo::Result<Optional<String>> {
+    fn device(&self) -> crate::Result<Optional<String>> {
         Ok(Optional::from(self.device.clone()))
     }
 
-    fn includes(&self) -> fdo::Result<Vec<String>> {
+    fn includes(&self) -> crate::Result<Vec<String>> {
         Ok(self.includes.clone())
     }
 
-    fn handle(&self) -> fdo::Result<Optional<u16>> {
+    fn handle(&self) -> crate::Result<Optional<u16>> {
         Ok(Optional::from(self.handle))
     }
 }
 
-#[proxy(interface = "org.bluez.GattCharacteristic1")]
+#[cfg(feature = "assigned-numbers")]
+impl GattService {
+    /// Returns the assigned name of the service, e.g. "Heart Rate".
+    pub fn name(&self) -> Option<&'static str> {
+        crate::assigned_numbers::service_name(&self.uuid)
+    }
+}
+
+#[proxy(
+    default_service = "org.bluez",
+    interface = "org.bluez.GattCharacteristic1"
+)]
 pub trait GattCharacteristic1 {
     async fn read_value(
         &self,
         options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
-    ) -> fdo::Result<Vec<u8>>;
+    ) -> crate::Result<Vec<u8>>;
 
     async fn write_value(
         &mut self,
         value: Vec<u8>,
         options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
-    ) -> fdo::Result<()>;
+    ) -> crate::Result<()>;
 
-    async fn start_notify(&mut self) -> fdo::Result<()>;
+    async fn start_notify(&mut self) -> crate::Result<()>;
 
-    async fn stop_notify(&mut self) -> fdo::Result<()>;
+    async fn stop_notify(&mut self) -> crate::Result<()>;
 
-    #[zbus(property)]
-    fn uuid(&self) -> fdo::Result<String>;
+    /// Acquires a `SOCK_SEQPACKET` socket for writing without response.
+    ///
+    /// # Returns
+    /// The socket and the maximum number of bytes per write.
+    async fn acquire_write(
+        &self,
+        options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+    ) -> crate::Result<(zbus::zvariant::OwnedFd, u16)>;
+
+    /// Acquires a `SOCK_SEQPACKET` socket receiving the notifications.
+    ///
+    /// # Returns
+    /// The socket and the maximum number of bytes per notification.
+    async fn acquire_notify(
+        &self,
+        options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+    ) -> crate::Result<(zbus::zvariant::OwnedFd, u16)>;
+
+    #[zbus(property, name = "UUID")]
+    fn uuid(&self) -> crate::Result<BtUuid>;
 
     #[zbus(property)]
-    fn service(&self) -> fdo::Result<String>;
+    fn service(&self) -> crate::Result<String>;
 
     #[zbus(property)]
-    fn value(&self) -> fdo::Result<Optional<Vec<u8>>>;
+    fn value(&self) -> crate::Result<Optional<Vec<u8>>>;
 
     #[zbus(property)]
-    fn write_acquired(&self) -> fdo::Result<bool>;
+    fn write_acquired(&self) -> crate::Result<bool>;
 
     #[zbus(property)]
-    fn notify_acquired(&self) -> fdo::Result<bool>;
+    fn notify_acquired(&self) -> crate::Result<bool>;
 
     #[zbus(property)]
-    fn notifying(&self) -> fdo::Result<bool>;
+    fn notifying(&self) -> crate::Result<bool>;
 
     #[zbus(property)]
-    fn flags(&self) -> fdo::Result<Vec<String>>;
+    fn flags(&self) -> crate::Result<Vec<String>>;
 
     #[zbus(property)]
-    fn handle(&self) -> fdo::Result<Optional<u16>>;
+    fn handle(&self) -> crate::Result<Optional<u16>>;
 
     #[zbus(property)]
-    fn mtu(&self) -> fdo::Result<Optional<u16>>;
+    fn mtu(&self) -> crate::Result<Optional<u16>>;
 }
 
 pub struct GattCharacteristic {
-    pub uuid: String,
+    pub uuid: BtUuid,
     pub service: String,
     pub value: Option<Vec<u8>>,
     pub write_acquired: bool,
@@ -113,7 +144,7 @@ impl GattCharacteristic {
     async fn read_value(
         &self,
         _options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
-    ) -> fdo::Result<Vec<u8>> {
+    ) -> crate::Result<Vec<u8>> {
         Ok(self.value.clone().unwrap_or_default())
     }
 
@@ -121,63 +152,163 @@ impl GattCharacteristic {
         &mut self,
         value: Vec<u8>,
         _options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
-    ) -> fdo::Result<()> {
+    ) -> crate::Result<()> {
         self.value = Some(value);
         Ok(())
     }
 
-    async fn start_notify(&mut self) -> fdo::Result<()> {
+    async fn start_notify(&mut self) -> crate::Result<()> {
         self.notifying = true;
         Ok(())
     }
 
-    async fn stop_notify(&mut self) -> fdo::Result<()> {
+    async fn stop_notify(&mut self) -> crate::Result<()> {
         self.notifying = false;
         Ok(())
     }
 
-    fn uuid(&self) -> fdo::Result<String> {
-        Ok(self.uuid.clone())
+    fn uuid(&self) -> crate::Result<BtUuid> {
+        Ok(self.uuid)
     }
 
-    fn service(&self) -> fdo::Result<String> {
+    fn service(&self) -> crate::Result<String> {
         Ok(self.service.clone())
     }
 
-    fn value(&self) -> fdo::Result<Optional<Vec<u8>>> {
+    fn value(&self) -> crate::Result<Optional<Vec<u8>>> {
         Ok(Optional::from(self.value.clone()))
     }
 
-    fn write_acquired(&self) -> fdo::Result<bool> {
+    fn write_acquired(&self) -> crate::Result<bool> {
         Ok(self.write_acquired)
     }
 
-    fn notify_acquired(&self) -> fdo::Result<bool> {
+    fn notify_acquired(&self) -> crate::Result<bool> {
         Ok(self.notify_acquired)
     }
 
-    fn notifying(&self) -> fdo::Result<bool> {
+    fn notifying(&self) -> crate::Result<bool> {
         Ok(self.notifying)
     }
 
-    fn flags(&self) -> fdo::Result<Vec<String>> {
+    fn flags(&self) -> crate::Result<Vec<String>> {
         Ok(self.flags.clone())
     }
 
-    fn handle(&self) -> fdo::Result<Optional<u16>> {
+    fn handle(&self) -> crate::Result<Optional<u16>> {
         Ok(Optional::from(self.handle))
     }
 
-    fn mtu(&self) -> fdo::Result<Optional<u16>> {
+    fn mtu(&self) -> crate::Result<Optional<u16>> {
         Ok(Optional::from(self.mtu))
     }
 }
 
+#[cfg(feature = "assigned-numbers")]
+impl GattCharacteristic {
+    /// Returns the assigned name of the characteristic, e.g. "Battery Level".
+    pub fn name(&self) -> Option<&'static str> {
+        crate::assigned_numbers::characteristic_name(&self.uuid)
+    }
+}
+
+/// Reads and writes the descriptors of remote characteristics, e.g. the
+/// Client Characteristic Configuration (`2902`).
+#[proxy(default_service = "org.bluez", interface = "org.bluez.GattDescriptor1")]
+pub trait GattDescriptor1 {
+    async fn read_value(
+        &self,
+        options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+    ) -> crate::Result<Vec<u8>>;
+
+    async fn write_value(
+        &self,
+        value: Vec<u8>,
+        options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+    ) -> crate::Result<()>;
+
+    #[zbus(property, name = "UUID")]
+    fn uuid(&self) -> crate::Result<BtUuid>;
+
+    /// Object path of the characteristic the descriptor belongs to.
+    #[zbus(property)]
+    fn characteristic(&self) -> crate::Result<zbus::zvariant::OwnedObjectPath>;
+
+    #[zbus(property)]
+    fn value(&self) -> crate::Result<Optional<Vec<u8>>>;
+
+    #[zbus(property)]
+    fn flags(&self) -> crate::Result<Vec<String>>;
+
+    #[zbus(property)]
+    fn handle(&self) -> crate::Result<Optional<u16>>;
+}
+
+/// A descriptor served by a local GATT application.
+///
+/// It is exported below its characteristic, e.g. at `.../char0000/desc0000`,
+/// with `characteristic` set to the characteristic's object path.
+pub struct GattDescriptor {
+    pub uuid: BtUuid,
+    /// Object path of the characteristic the descriptor belongs to.
+    pub characteristic: zbus::zvariant::OwnedObjectPath,
+    pub value: Vec<u8>,
+    /// Access flags, e.g. `"read"` or `"write"`.
+    pub flags: Vec<String>,
+}
+
+#[interface(name = "org.bluez.GattDescriptor1")]
+impl GattDescriptor {
+    async fn read_value(
+        &self,
+        _options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+    ) -> crate::Result<Vec<u8>> {
+        Ok(self.value.clone())
+    }
+
+    async fn write_value(
+        &mut self,
+        value: Vec<u8>,
+        _options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+    ) -> crate::Result<()> {
+        self.value = value;
+        Ok(())
+    }
+
+    #[zbus(property, name = "UUID")]
+    fn uuid(&self) -> BtUuid {
+        self.uuid
+    }
+
+    #[zbus(property)]
+    fn characteristic(&self) -> zbus::zvariant::OwnedObjectPath {
+        self.characteristic.clone()
+    }
+
+    #[zbus(property)]
+    fn value(&self) -> Vec<u8> {
+        self.value.clone()
+    }
+
+    #[zbus(property)]
+    fn flags(&self) -> Vec<String> {
+        self.flags.clone()
+    }
+}
+
+#[cfg(feature = "assigned-numbers")]
+impl GattDescriptor {
+    /// Returns the assigned name of the descriptor, e.g. "Characteristic User Description".
+    pub fn name(&self) -> Option<&'static str> {
+        crate::assigned_numbers::descriptor_name(&self.uuid)
+    }
+}
+
 pub async fn register_service(
     connection: &Connection,
     service_path: &str,
     service: impl Interface + 'static,
-) -> fdo::Result<()> {
+) -> crate::Result<()> {
     connection.object_server().at(service_path, service).await?;
     Ok(())
 }
@@ -186,10 +317,86 @@ pub async fn register_characteristic(
     connection: &Connection,
     characteristic_path: &str,
     characteristic: impl Interface + 'static,
-) -> fdo::Result<()> {
+) -> crate::Result<()> {
     connection
         .object_server()
         .at(characteristic_path, characteristic)
         .await?;
     Ok(())
 }
+
+/// Exports a GATT descriptor at `descriptor_path`, usually below its characteristic.
+pub async fn register_descriptor(
+    connection: &Connection,
+    descriptor_path: &str,
+    descriptor: impl Interface + 'static,
+) -> crate::Result<()> {
+    connection
+        .object_server()
+        .at(descriptor_path, descriptor)
+        .await?;
+    Ok(())
+}
+
+/// Defines the `GattManager1` trait for registering GATT applications with an adapter.
+#[proxy(default_service = "org.bluez", interface = "org.bluez.GattManager1")]
+pub trait GattManager1 {
+    async fn register_application(
+        &self,
+        application: &zbus::zvariant::ObjectPath<'_>,
+        options: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+    ) -> crate::Result<()>;
+
+    async fn unregister_application(
+        &self,
+        application: &zbus::zvariant::ObjectPath<'_>,
+    ) -> crate::Result<()>;
+}
+
+/// Registers the GATT application rooted at `application_path` with the adapter.
+///
+/// An `org.freedesktop.DBus.ObjectManager` is exported at `application_path`;
+/// services, characteristics and descriptors must be registered below it beforehand.
+pub async fn register_application(
+    connection: &Connection,
+    adapter_path: &str,
+    application_path: &str,
+) -> crate::Result<()> {
+    let application = zbus::zvariant::ObjectPath::try_from(application_path)?;
+    connection
+        .object_server()
+        .at(&application, zbus::fdo::ObjectManager)
+        .await?;
+
+    announce_application(connection, adapter_path, &application).await
+}
+
+/// Registers the GATT application already exported at `application` with the adapter.
+pub(crate) async fn announce_application(
+    connection: &Connection,
+    adapter_path: &str,
+    application: &zbus::zvariant::ObjectPath<'_>,
+) -> crate::Result<()> {
+    let manager = GattManager1Proxy::builder(connection)
+        .path(adapter_path)?
+        .build()
+        .await?;
+    manager
+        .register_application(application, std::collections::HashMap::new())
+        .await
+}
+
+/// Unregisters the GATT application rooted at `application_path` from the adapter.
+pub async fn unregister_application(
+    connection: &Connection,
+    adapter_path: &str,
+    application_path: &str,
+) -> crate::Result<()> {
+    let manager = GattManager1Proxy::builder(connection)
+        .path(adapter_path)?
+        .build()
+        .await?;
+    manager
+        .unregister_application(&zbus::zvariant::ObjectPath::try_from(application_path)?)
+        .await
+}
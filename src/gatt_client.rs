@@ -0,0 +1,506 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::cache::convert;
+use crate::notifications::NotifySessions;
+use crate::object_manager::ObjectsMap;
+use crate::{
+    BtUuid, DeviceProxy, GattCharacteristic1Proxy, GattDescriptor1Proxy, GattService1Proxy,
+};
+
+/// A GATT service of a remote device.
+#[derive(Debug, Clone)]
+pub struct RemoteService {
+    /// Object path of the service, e.g. `/org/bluez/hci0/dev_00_11_22_33_44_55/service000a`.
+    pub path: OwnedObjectPath,
+    pub uuid: BtUuid,
+    pub primary: bool,
+    pub handle: Option<u16>,
+    /// Characteristics of the service, ordered by object path.
+    pub characteristics: Vec<RemoteCharacteristic>,
+    /// Connection the service was discovered on.
+    connection: zbus::Connection,
+}
+
+impl RemoteService {
+    /// Returns the first characteristic with `uuid`.
+    pub fn characteristic(&self, uuid: BtUuid) -> Option<&RemoteCharacteristic> {
+        self.characteristics
+            .iter()
+            .find(|characteristic| characteristic.uuid == uuid)
+    }
+
+    /// Creates a proxy for the service.
+    pub async fn proxy(&self) -> crate::Result<GattService1Proxy<'static>> {
+        Ok(GattService1Proxy::builder(&self.connection)
+            .path(self.path.clone())?
+            .build()
+            .await?)
+    }
+}
+
+/// A GATT characteristic of a remote device.
+#[derive(Debug, Clone)]
+pub struct RemoteCharacteristic {
+    /// Object path of the characteristic.
+    pub path: OwnedObjectPath,
+    pub uuid: BtUuid,
+    /// Object path of the service the characteristic belongs to.
+    pub service: OwnedObjectPath,
+    /// Access flags, e.g. `"read"`, `"write-without-response"` or `"notify"`.
+    pub flags: Vec<String>,
+    pub handle: Option<u16>,
+    pub mtu: Option<u16>,
+    /// Descriptors of the characteristic, ordered by object path.
+    pub descriptors: Vec<RemoteDescriptor>,
+    /// Connection the characteristic was discovered on.
+    pub(crate) connection: zbus::Connection,
+    /// Notification sessions shared with the other characteristics of the device.
+    pub(crate) sessions: Arc<NotifySessions>,
+}
+
+impl RemoteCharacteristic {
+    /// Returns the first descriptor with `uuid`.
+    pub fn descriptor(&self, uuid: BtUuid) -> Option<&RemoteDescriptor> {
+        self.descriptors
+            .iter()
+            .find(|descriptor| descriptor.uuid == uuid)
+    }
+
+    /// Returns whether the characteristic has the access flag `flag`, e.g. `"notify"`.
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    /// Creates a proxy for the characteristic.
+    pub async fn proxy(&self) -> crate::Result<GattCharacteristic1Proxy<'static>> {
+        Ok(GattCharacteristic1Proxy::builder(&self.connection)
+            .path(self.path.clone())?
+            .build()
+            .await?)
+    }
+
+    /// Reads the value of the characteristic from the device.
+    pub async fn read(&self) -> crate::Result<Vec<u8>> {
+        self.proxy().await?.read_value(HashMap::new()).await
+    }
+
+    /// Writes `value` to the characteristic.
+    ///
+    /// # Arguments
+    /// * `value` - The bytes to write.
+    pub async fn write(&self, value: &[u8]) -> crate::Result<()> {
+        self.proxy()
+            .await?
+            .write_value(value.to_vec(), HashMap::new())
+            .await
+    }
+}
+
+/// A GATT descriptor of a remote device.
+#[derive(Debug, Clone)]
+pub struct RemoteDescriptor {
+    /// Object path of the descriptor.
+    pub path: OwnedObjectPath,
+    pub uuid: BtUuid,
+    /// Object path of the characteristic the descriptor belongs to.
+    pub characteristic: OwnedObjectPath,
+    /// Access flags, e.g. `"read"` or `"write"`.
+    pub flags: Vec<String>,
+    pub handle: Option<u16>,
+    /// Connection the descriptor was discovered on.
+    connection: zbus::Connection,
+}
+
+impl RemoteDescriptor {
+    /// Creates a proxy for the descriptor.
+    pub async fn proxy(&self) -> crate::Result<GattDescriptor1Proxy<'static>> {
+        Ok(GattDescriptor1Proxy::builder(&self.connection)
+            .path(self.path.clone())?
+            .build()
+            .await?)
+    }
+
+    /// Reads the value of the descriptor from the device.
+    pub async fn read(&self) -> crate::Result<Vec<u8>> {
+        self.proxy().await?.read_value(HashMap::new()).await
+    }
+
+    /// Writes `value` to the descriptor.
+    ///
+    /// # Arguments
+    /// * `value` - The bytes to write.
+    pub async fn write(&self, value: &[u8]) -> crate::Result<()> {
+        self.proxy()
+            .await?
+            .write_value(value.to_vec(), HashMap::new())
+            .await
+    }
+}
+
+/// The GATT services of a remote device with their characteristics and descriptors.
+#[derive(Debug, Clone, Default)]
+pub struct GattServices {
+    /// Services ordered by object path.
+    pub services: Vec<RemoteService>,
+}
+
+impl GattServices {
+    /// Builds the tree of the device at `device_path` from BlueZ's managed objects.
+    ///
+    /// # Arguments
+    /// * `connection` - The connection used by proxies created from the tree.
+    /// * `device_path` - Object path of the device.
+    /// * `objects` - The result of `GetManagedObjects`.
+    pub(crate) fn from_objects(
+        connection: &zbus::Connection,
+        device_path: &str,
+        objects: &ObjectsMap,
+    ) -> Self {
+        let prefix = format!("{}/", device_path);
+        let mut objects: Vec<_> = objects
+            .iter()
+            .filter(|(path, _)| path.as_str().starts_with(&prefix))
+            .collect();
+        objects.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        let sessions = Arc::new(NotifySessions::default());
+
+        let mut descriptors: Vec<RemoteDescriptor> = objects
+            .iter()
+            .filter_map(|(path, interfaces)| {
+                let properties = interfaces.get("org.bluez.GattDescriptor1")?;
+                Some(RemoteDescriptor {
+                    path: (*path).clone(),
+                    uuid: convert(properties.get("UUID")?)?,
+                    characteristic: convert(properties.get("Characteristic")?)?,
+                    flags: properties
+                        .get("Flags")
+                        .and_then(|value| convert(value))
+                        .unwrap_or_default(),
+                    handle: properties.get("Handle").and_then(|value| convert(value)),
+                    connection: connection.clone(),
+                })
+            })
+            .collect();
+
+        let mut characteristics: Vec<RemoteCharacteristic> = objects
+            .iter()
+            .filter_map(|(path, interfaces)| {
+                let properties = interfaces.get("org.bluez.GattCharacteristic1")?;
+                let (own, rest) = descriptors
+                    .drain(..)
+                    .partition(|descriptor| &descriptor.characteristic == *path);
+                descriptors = rest;
+                Some(RemoteCharacteristic {
+                    path: (*path).clone(),
+                    uuid: convert(properties.get("UUID")?)?,
+                    service: convert(properties.get("Service")?)?,
+                    flags: properties
+                        .get("Flags")
+                        .and_then(|value| convert(value))
+                        .unwrap_or_default(),
+                    handle: properties.get("Handle").and_then(|value| convert(value)),
+                    mtu: properties.get("MTU").and_then(|value| convert(value)),
+                    descriptors: own,
+                    connection: connection.clone(),
+                    sessions: sessions.clone(),
+                })
+            })
+            .collect();
+
+        let services = objects
+            .iter()
+            .filter_map(|(path, interfaces)| {
+                let properties = interfaces.get("org.bluez.GattService1")?;
+                let (own, rest) = characteristics
+                    .drain(..)
+                    .partition(|characteristic| &characteristic.service == *path);
+                characteristics = rest;
+                Some(RemoteService {
+                    path: (*path).clone(),
+                    uuid: convert(properties.get("UUID")?)?,
+                    primary: properties
+                        .get("Primary")
+                        .and_then(|value| convert(value))
+                        .unwrap_or(false),
+                    handle: properties.get("Handle").and_then(|value| convert(value)),
+                    characteristics: own,
+                    connection: connection.clone(),
+                })
+            })
+            .collect();
+
+        Self { services }
+    }
+
+    /// Returns the first service with `uuid`.
+    pub fn service(&self, uuid: BtUuid) -> Option<&RemoteService> {
+        self.services.iter().find(|service| service.uuid == uuid)
+    }
+
+    /// Returns the first characteristic with `uuid` in any service.
+    pub fn characteristic(&self, uuid: BtUuid) -> Option<&RemoteCharacteristic> {
+        self.characteristics()
+            .find(|characteristic| characteristic.uuid == uuid)
+    }
+
+    /// Iterates over the characteristics of every service.
+    pub fn characteristics(&self) -> impl Iterator<Item = &RemoteCharacteristic> {
+        self.services
+            .iter()
+            .flat_map(|service| service.characteristics.iter())
+    }
+}
+
+impl DeviceProxy<'_> {
+    /// Waits until BlueZ has resolved the services of the connected device.
+    ///
+    /// # Errors
+    /// [`crate::Error::NotConnected`] if the device is or becomes disconnected first.
+    pub async fn wait_services_resolved(&self) -> crate::Result<()> {
+        let mut resolved = self.receive_services_resolved_changed().await;
+        let mut connected = self.receive_connected_changed().await;
+        let not_connected = || crate::Error::NotConnected("device is not connected".into());
+
+        if self.services_resolved().await? {
+            return Ok(());
+        }
+        if !self.connected().await? {
+            return Err(not_connected());
+        }
+
+        loop {
+            tokio::select! {
+                change = resolved.next() => match change {
+                    Some(change) if change.get().await? => return Ok(()),
+                    Some(_) => {}
+                    None => return Err(not_connected()),
+                },
+                change = connected.next() => match change {
+                    Some(change) if !change.get().await? => return Err(not_connected()),
+                    Some(_) => {}
+                    None => return Err(not_connected()),
+                },
+            }
+        }
+    }
+
+    /// Waits for the device's services to be resolved and returns them.
+    pub async fn gatt_services(&self) -> crate::Result<GattServices> {
+        self.wait_services_resolved().await?;
+
+        let connection = self.inner().connection();
+        let objects = crate::ObjectManagerProxy::new(connection)
+            .await?
+            .get_managed_objects()
+            .await?;
+        Ok(GattServices::from_objects(
+            connection,
+            self.inner().path().as_str(),
+            &objects,
+        ))
+    }
+
+    /// Waits for the device's services to be resolved and returns the service with `uuid`.
+    ///
+    /// # Errors
+    /// [`crate::Error::DoesNotExist`] if the device has no such service.
+    pub async fn service(&self, uuid: BtUuid) -> crate::Result<RemoteService> {
+        self.gatt_services()
+            .await?
+            .service(uuid)
+            .cloned()
+            .ok_or_else(|| crate::Error::DoesNotExist(format!("no service {}", uuid)))
+    }
+
+    /// Waits for the device's services to be resolved and returns the
+    /// characteristic with `uuid`.
+    ///
+    /// # Errors
+    /// [`crate::Error::DoesNotExist`] if the device has no such characteristic.
+    pub async fn characteristic(&self, uuid: BtUuid) -> crate::Result<RemoteCharacteristic> {
+        self.gatt_services()
+            .await?
+            .characteristic(uuid)
+            .cloned()
+            .ok_or_else(|| crate::Error::DoesNotExist(format!("no characteristic {}", uuid)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+
+    use super::*;
+
+    const DEVICE: &str = "/org/bluez/hci0/dev_00_11_22_33_44_55";
+
+    /// Creates a connection to a peer in this process, which no test talks to.
+    async fn connection() -> zbus::Connection {
+        let (server, client) = std::os::unix::net::UnixStream::pair().unwrap();
+        let guid = zbus::Guid::generate();
+        let server = zbus::connection::Builder::async_io_unix_stream(server)
+            .server(guid)
+            .unwrap()
+            .p2p()
+            .build();
+        let client = zbus::connection::Builder::async_io_unix_stream(client)
+            .p2p()
+            .build();
+        let (_server, client) = futures::try_join!(server, client).unwrap();
+        client
+    }
+
+    fn value<'a>(value: impl Into<Value<'a>>) -> OwnedValue {
+        OwnedValue::try_from(value.into()).unwrap()
+    }
+
+    fn path(path: &str) -> OwnedValue {
+        value(ObjectPath::try_from(path).unwrap())
+    }
+
+    fn object(
+        objects: &mut ObjectsMap,
+        path: &str,
+        interface: &str,
+        properties: Vec<(&str, OwnedValue)>,
+    ) {
+        let properties = properties
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        objects.insert(
+            OwnedObjectPath::try_from(path).unwrap(),
+            HashMap::from([(interface.to_string(), properties)]),
+        );
+    }
+
+    fn fixture() -> ObjectsMap {
+        let mut objects = ObjectsMap::new();
+        let service = |uuid: &str, handle: u16| {
+            vec![
+                ("UUID", value(uuid)),
+                ("Primary", value(true)),
+                ("Handle", value(handle)),
+            ]
+        };
+        let characteristic = |uuid: &str, service: &str, flags: &[&str]| {
+            vec![
+                ("UUID", value(uuid)),
+                ("Service", path(&format!("{}/{}", DEVICE, service))),
+                ("Flags", value(flags.to_vec())),
+                ("MTU", value(23u16)),
+            ]
+        };
+        let descriptor = |uuid: &str, characteristic: &str| {
+            vec![
+                ("UUID", value(uuid)),
+                (
+                    "Characteristic",
+                    path(&format!("{}/{}", DEVICE, characteristic)),
+                ),
+            ]
+        };
+
+        object(
+            &mut objects,
+            &format!("{}/service0010/char0011/desc0013", DEVICE),
+            "org.bluez.GattDescriptor1",
+            descriptor("2904", "service0010/char0011"),
+        );
+        object(
+            &mut objects,
+            &format!("{}/service0010/char0011", DEVICE),
+            "org.bluez.GattCharacteristic1",
+            characteristic("2a19", "service0010", &["read", "notify"]),
+        );
+        object(
+            &mut objects,
+            &format!("{}/service000a/char000e", DEVICE),
+            "org.bluez.GattCharacteristic1",
+            characteristic("2a38", "service000a", &["read"]),
+        );
+        object(
+            &mut objects,
+            &format!("{}/service000a/char000b/desc00ff", DEVICE),
+            "org.bluez.GattDescriptor1",
+            descriptor("2902", "service000a/char00ff"),
+        );
+        object(
+            &mut objects,
+            &format!("{}/service000a/char000b/desc000d", DEVICE),
+            "org.bluez.GattDescriptor1",
+            descriptor("2902", "service000a/char000b"),
+        );
+        object(
+            &mut objects,
+            &format!("{}/service000a/char000b", DEVICE),
+            "org.bluez.GattCharacteristic1",
+            characteristic("2a37", "service000a", &["notify"]),
+        );
+        object(
+            &mut objects,
+            &format!("{}/service0010", DEVICE),
+            "org.bluez.GattService1",
+            service("180f", 0x10),
+        );
+        object(
+            &mut objects,
+            &format!("{}/service000a", DEVICE),
+            "org.bluez.GattService1",
+            service("180d", 0x0a),
+        );
+        object(
+            &mut objects,
+            DEVICE,
+            "org.bluez.Device1",
+            vec![("Address", value("00:11:22:33:44:55"))],
+        );
+        object(
+            &mut objects,
+            "/org/bluez/hci0/dev_66_77_88_99_AA_BB/service0001",
+            "org.bluez.GattService1",
+            service("1800", 0x01),
+        );
+        objects
+    }
+
+    #[tokio::test]
+    async fn from_objects() {
+        let services = GattServices::from_objects(&connection().await, DEVICE, &fixture());
+
+        let uuids: Vec<_> = services.services.iter().map(|s| s.uuid).collect();
+        assert_eq!(uuids, [BtUuid::from_u16(0x180d), BtUuid::from_u16(0x180f)]);
+
+        let heart_rate = &services.services[0];
+        assert_eq!(heart_rate.path.as_str(), format!("{}/service000a", DEVICE));
+        assert!(heart_rate.primary);
+        assert_eq!(heart_rate.handle, Some(0x0a));
+        let uuids: Vec<_> = heart_rate.characteristics.iter().map(|c| c.uuid).collect();
+        assert_eq!(uuids, [BtUuid::from_u16(0x2a37), BtUuid::from_u16(0x2a38)]);
+
+        let measurement = &heart_rate.characteristics[0];
+        assert_eq!(measurement.service, heart_rate.path);
+        assert_eq!(measurement.flags, ["notify"]);
+        assert_eq!(measurement.mtu, Some(23));
+        assert_eq!(measurement.handle, None);
+        assert_eq!(measurement.descriptors.len(), 1);
+        assert_eq!(
+            measurement.descriptors[0].path.as_str(),
+            format!("{}/service000a/char000b/desc000d", DEVICE)
+        );
+        assert!(heart_rate.characteristics[1].descriptors.is_empty());
+
+        let battery = services.characteristic(BtUuid::from_u16(0x2a19)).unwrap();
+        assert!(battery.has_flag("read") && battery.has_flag("notify"));
+        assert!(battery.descriptor(BtUuid::from_u16(0x2904)).is_some());
+        assert_eq!(services.characteristics().count(), 3);
+        assert!(Arc::ptr_eq(&measurement.sessions, &battery.sessions));
+        assert!(services.service(BtUuid::from_u16(0x1800)).is_none());
+    }
+}
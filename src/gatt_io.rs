@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::{FutureExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::UnixDatagram;
+use zbus::zvariant::{OwnedValue, Value};
+
+use crate::{Notification, RemoteCharacteristic};
+
+/// Payload size of a write or notification with the default ATT MTU of 23.
+const DEFAULT_PAYLOAD_SIZE: usize = 20;
+
+/// Writes to a remote characteristic as an [`AsyncWrite`].
+///
+/// Each write sends at most [`CharacteristicWriter::mtu`] bytes as one packet.
+/// It goes through the socket returned by `AcquireWrite` when the
+/// characteristic supports write without response, and through `WriteValue`
+/// otherwise.
+pub struct CharacteristicWriter {
+    /// Maximum number of bytes sent per packet.
+    mtu: usize,
+    inner: WriterInner,
+}
+
+enum WriterInner {
+    /// The socket returned by `AcquireWrite`.
+    Socket(UnixDatagram),
+    /// `WriteValue` calls, one at a time.
+    WriteValue {
+        proxy: crate::GattCharacteristic1Proxy<'static>,
+        options: HashMap<String, OwnedValue>,
+        /// Length and future of the write in progress.
+        pending: Option<(usize, BoxFuture<'static, crate::Result<()>>)>,
+    },
+}
+
+impl CharacteristicWriter {
+    /// Returns the maximum number of bytes sent per packet.
+    pub fn mtu(&self) -> usize {
+        self.mtu
+    }
+
+    /// Checks if the writer uses the socket returned by `AcquireWrite`.
+    pub fn is_acquired(&self) -> bool {
+        matches!(self.inner, WriterInner::Socket(_))
+    }
+}
+
+impl AsyncWrite for CharacteristicWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let packet = &buf[..buf.len().min(self.mtu)];
+        match &mut self.inner {
+            WriterInner::Socket(socket) => socket.poll_send(cx, packet),
+            WriterInner::WriteValue {
+                proxy,
+                options,
+                pending,
+            } => {
+                let (len, write) = pending.get_or_insert_with(|| {
+                    let mut proxy = proxy.clone();
+                    let value = packet.to_vec();
+                    let options = clone_options(options);
+                    let write = async move { proxy.write_value(value, options?).await };
+                    (packet.len(), write.boxed())
+                });
+                let result = ready!(write.poll_unpin(cx));
+                let len = *len;
+                *pending = None;
+                Poll::Ready(result.map(|()| len).map_err(io::Error::other))
+            }
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match &mut self.inner {
+            WriterInner::Socket(_) => Poll::Ready(Ok(())),
+            WriterInner::WriteValue { pending, .. } => {
+                if let Some((_, write)) = pending {
+                    let result = ready!(write.poll_unpin(cx));
+                    *pending = None;
+                    result.map_err(io::Error::other)?;
+                }
+                Poll::Ready(Ok(()))
+            }
+        }
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.as_mut().poll_flush(cx))?;
+        match &self.inner {
+            WriterInner::Socket(socket) => Poll::Ready(socket.shutdown(std::net::Shutdown::Write)),
+            WriterInner::WriteValue { .. } => Poll::Ready(Ok(())),
+        }
+    }
+}
+
+/// Reads the notifications of a remote characteristic as an [`AsyncRead`].
+///
+/// Each read returns bytes of at most one notification; a notification longer
+/// than the read buffer is returned over several reads. It reads from the
+/// socket returned by `AcquireNotify` when the characteristic supports it, and
+/// from a [`RemoteCharacteristic::notifications`] stream otherwise.
+///
+/// Empty notifications from the stream are skipped. The socket cannot tell an
+/// empty notification from being closed, so the reader ends on either.
+pub struct CharacteristicReader {
+    /// Maximum number of bytes per notification.
+    mtu: usize,
+    inner: ReaderInner,
+    /// Unread rest of the last notification.
+    rest: Vec<u8>,
+}
+
+enum ReaderInner {
+    /// The socket returned by `AcquireNotify`.
+    Socket(UnixDatagram),
+    /// Notifications delivered through `PropertiesChanged`.
+    Notifications(BoxStream<'static, Notification>),
+}
+
+impl CharacteristicReader {
+    /// Returns the maximum number of bytes per notification.
+    pub fn mtu(&self) -> usize {
+        self.mtu
+    }
+
+    /// Checks if the reader uses the socket returned by `AcquireNotify`.
+    pub fn is_acquired(&self) -> bool {
+        matches!(self.inner, ReaderInner::Socket(_))
+    }
+
+    /// Receives the next non-empty notification into `rest`.
+    ///
+    /// Leaves `rest` empty once the socket was closed or the stream ended.
+    fn poll_notification(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            match &mut self.inner {
+                ReaderInner::Socket(socket) => {
+                    // A SOCK_SEQPACKET socket reports its end as an empty packet.
+                    self.rest.resize(self.mtu, 0);
+                    let mut packet = ReadBuf::new(&mut self.rest);
+                    let result = socket.poll_recv(cx, &mut packet);
+                    let len = packet.filled().len();
+                    self.rest.truncate(len);
+                    return result;
+                }
+                ReaderInner::Notifications(notifications) => {
+                    match ready!(notifications.poll_next_unpin(cx)) {
+                        Some(notification) if notification.value.is_empty() => continue,
+                        Some(notification) => self.rest = notification.value,
+                        None => {}
+                    }
+                    return Poll::Ready(Ok(()));
+                }
+            }
+        }
+    }
+}
+
+impl AsyncRead for CharacteristicReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        if self.rest.is_empty() {
+            ready!(self.poll_notification(cx))?;
+        }
+        let len = self.rest.len().min(buf.remaining());
+        buf.put_slice(&self.rest[..len]);
+        self.rest.drain(..len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl RemoteCharacteristic {
+    /// Opens the characteristic for writing.
+    ///
+    /// Characteristics with the `write-without-response` flag are written
+    /// through the socket returned by `AcquireWrite`, bypassing D-Bus; others
+    /// and characteristics BlueZ refuses to hand out a socket for fall back
+    /// to `WriteValue`.
+    pub async fn writer(&self) -> crate::Result<CharacteristicWriter> {
+        let proxy = self.proxy().await?;
+        let without_response = self.has_flag("write-without-response");
+
+        if without_response {
+            match proxy.acquire_write(HashMap::new()).await {
+                Ok((fd, mtu)) => {
+                    return Ok(CharacteristicWriter {
+                        mtu: payload_size(Some(mtu)),
+                        inner: WriterInner::Socket(socket(fd)?),
+                    })
+                }
+                Err(crate::Error::NotSupported(_)) | Err(crate::Error::NotPermitted(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        let mut options = HashMap::new();
+        if without_response {
+            options.insert("type".to_string(), Value::from("command").try_into()?);
+        }
+        Ok(CharacteristicWriter {
+            mtu: payload_size(self.mtu),
+            inner: WriterInner::WriteValue {
+                proxy,
+                options,
+                pending: None,
+            },
+        })
+    }
+
+    /// Opens the characteristic for reading its notifications.
+    ///
+    /// The socket returned by `AcquireNotify` is used when BlueZ hands one
+    /// out; otherwise notifications are received with
+    /// [`RemoteCharacteristic::notifications`].
+    pub async fn reader(&self) -> crate::Result<CharacteristicReader> {
+        if self.has_flag("notify") {
+            let proxy = self.proxy().await?;
+            match proxy.acquire_notify(HashMap::new()).await {
+                Ok((fd, mtu)) => {
+                    return Ok(CharacteristicReader {
+                        mtu: payload_size(Some(mtu)),
+                        inner: ReaderInner::Socket(socket(fd)?),
+                        rest: Vec::new(),
+                    })
+                }
+                Err(crate::Error::NotSupported(_)) | Err(crate::Error::NotPermitted(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(CharacteristicReader {
+            mtu: payload_size(self.mtu),
+            inner: ReaderInner::Notifications(self.notifications().await?.boxed()),
+            rest: Vec::new(),
+        })
+    }
+}
+
+/// Returns the largest value fitting in one ATT packet of an ATT MTU.
+///
+/// Both the `MTU` property and the MTU returned by `AcquireWrite` and
+/// `AcquireNotify` include the 3-byte ATT header.
+fn payload_size(mtu: Option<u16>) -> usize {
+    mtu.map_or(DEFAULT_PAYLOAD_SIZE, |mtu| {
+        usize::from(mtu.saturating_sub(3)).max(1)
+    })
+}
+
+/// Wraps an acquired `SOCK_SEQPACKET` file descriptor.
+fn socket(fd: zbus::zvariant::OwnedFd) -> crate::Result<UnixDatagram> {
+    let socket = std::os::unix::net::UnixDatagram::from(std::os::fd::OwnedFd::from(fd));
+    socket
+        .set_nonblocking(true)
+        .and_then(|()| UnixDatagram::from_std(socket))
+        .map_err(|error| crate::Error::Failed(error.to_string()))
+}
+
+/// Copies `WriteValue` options for another call.
+fn clone_options(
+    options: &HashMap<String, OwnedValue>,
+) -> crate::Result<HashMap<String, OwnedValue>> {
+    options
+        .iter()
+        .map(|(key, value)| Ok((key.clone(), value.try_clone()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    fn reader(values: Vec<Vec<u8>>) -> CharacteristicReader {
+        let notifications = values.into_iter().map(|value| Notification {
+            value,
+            timestamp: SystemTime::now(),
+            missed: 0,
+        });
+        CharacteristicReader {
+            mtu: 8,
+            inner: ReaderInner::Notifications(futures::stream::iter(notifications).boxed()),
+            rest: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_notifications_in_chunks() {
+        let mut reader = reader(vec![vec![1, 2, 3, 4, 5], vec![], vec![6], vec![7, 8]]);
+        let mut buf = [0; 3];
+
+        let mut reads = Vec::new();
+        loop {
+            let len = reader.read(&mut buf).await.unwrap();
+            if len == 0 {
+                break;
+            }
+            reads.push(buf[..len].to_vec());
+        }
+
+        assert_eq!(reads, [vec![1, 2, 3], vec![4, 5], vec![6], vec![7, 8]]);
+    }
+
+    #[tokio::test]
+    async fn reads_socket_packets_in_chunks() {
+        let (local, remote) = UnixDatagram::pair().unwrap();
+        let mut reader = CharacteristicReader {
+            mtu: 8,
+            inner: ReaderInner::Socket(local),
+            rest: Vec::new(),
+        };
+        remote.send(&[1, 2, 3, 4, 5]).await.unwrap();
+        remote.send(&[6]).await.unwrap();
+
+        let mut buf = [0; 3];
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 3);
+        assert_eq!(buf, [1, 2, 3]);
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 2);
+        assert_eq!(buf[..2], [4, 5]);
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 1);
+        assert_eq!(buf[..1], [6]);
+    }
+
+    #[tokio::test]
+    async fn writes_socket_packets() {
+        let (local, remote) = UnixDatagram::pair().unwrap();
+        let mut writer = CharacteristicWriter {
+            mtu: 4,
+            inner: WriterInner::Socket(local),
+        };
+
+        assert_eq!(writer.write(&[]).await.unwrap(), 0);
+        assert_eq!(writer.write(&[1, 2, 3, 4, 5, 6]).await.unwrap(), 4);
+        writer.write_all(&[7, 8]).await.unwrap();
+
+        let mut buf = [0; 8];
+        assert_eq!(remote.recv(&mut buf).await.unwrap(), 4);
+        assert_eq!(buf[..4], [1, 2, 3, 4]);
+        assert_eq!(remote.recv(&mut buf).await.unwrap(), 2);
+        assert_eq!(buf[..2], [7, 8]);
+    }
+
+    #[tokio::test]
+    async fn splits_packets_at_att_payload_size() {
+        assert_eq!(payload_size(None), DEFAULT_PAYLOAD_SIZE);
+        assert_eq!(payload_size(Some(2)), 1);
+
+        let (local, remote) = UnixDatagram::pair().unwrap();
+        let mut writer = CharacteristicWriter {
+            mtu: payload_size(Some(23)),
+            inner: WriterInner::Socket(local),
+        };
+        let value: Vec<u8> = (0..45).collect();
+        writer.write_all(&value).await.unwrap();
+
+        let mut packets = Vec::new();
+        let mut buf = [0; 64];
+        while packets.iter().map(Vec::len).sum::<usize>() < value.len() {
+            let len = remote.recv(&mut buf).await.unwrap();
+            packets.push(buf[..len].to_vec());
+        }
+        assert_eq!(
+            packets.iter().map(Vec::len).collect::<Vec<_>>(),
+            [20, 20, 5]
+        );
+        assert_eq!(packets.concat(), value);
+    }
+}
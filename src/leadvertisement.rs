@@ -4,14 +4,16 @@ use zbus::object_server::Interface;
 use zbus::zvariant::{ObjectPath, Optional, OwnedValue, Value};
 use zbus::{fdo, interface};
 
+use crate::BtUuid;
+
 #[allow(non_snake_case)]
 pub struct LEAdvertisementProperties {
     Type: String,
     LocalName: Optional<String>,
-    ServiceUUIDs: Vec<String>,
-    SolicitUUIDs: Vec<String>,
+    ServiceUUIDs: Vec<BtUuid>,
+    SolicitUUIDs: Vec<BtUuid>,
     ManufacturerData: HashMap<u16, OwnedValue>,
-    ServiceData: HashMap<String, OwnedValue>,
+    ServiceData: HashMap<BtUuid, OwnedValue>,
     Data: HashMap<u8, OwnedValue>,
     Discoverable: Optional<bool>,
     DiscoverableTimeout: Optional<u16>,
@@ -38,12 +40,12 @@ impl LEAdvertisementProperties {
     }
 
     #[zbus(property)]
-    fn service_uuids(&self) -> fdo::Result<Vec<String>> {
+    fn service_uuids(&self) -> fdo::Result<Vec<BtUuid>> {
         Ok(self.ServiceUUIDs.clone())
     }
 
     #[zbus(property)]
-    fn solicit_uuids(&self) -> fdo::Result<Vec<String>> {
+    fn solicit_uuids(&self) -> fdo::Result<Vec<BtUuid>> {
         Ok(self.SolicitUUIDs.clone())
     }
 
@@ -53,7 +55,7 @@ impl LEAdvertisementProperties {
     }
 
     #[zbus(property)]
-    fn service_data(&self) -> fdo::Result<HashMap<String, OwnedValue>> {
+    fn service_data(&self) -> fdo::Result<HashMap<BtUuid, OwnedValue>> {
         Ok(self.ServiceData.clone())
     }
 
@@ -123,11 +125,11 @@ impl Default for LEAdvertisementProperties {
         Self {
             Type: "peripheral".to_string(), // Must be "broadcast" or "peripheral"
             LocalName: Optional::from(Some("MyDevice".to_string())), // Optional local name
-            ServiceUUIDs: vec!["180D".to_string(), "180F".to_string()], // List of service UUIDs
-            SolicitUUIDs: Vec::new(),       // Optional solicit UUIDs
+            ServiceUUIDs: vec![BtUuid::from_u16(0x180d), BtUuid::from_u16(0x180f)], // List of service UUIDs
+            SolicitUUIDs: Vec::new(),         // Optional solicit UUIDs
             ManufacturerData: HashMap::new(), // Optional manufacturer data
-            ServiceData: HashMap::new(),    // Optional service data
-            Data: HashMap::new(),           // Optional data
+            ServiceData: HashMap::new(),      // Optional service data
+            Data: HashMap::new(),             // Optional data
             Discoverable: Optional::from(Some(true)), // Optional discoverable flag
             DiscoverableTimeout: Optional::from(Some(30)), // Optional discoverable timeout
             Includes: vec!["tx-power".to_string()], // Optional includes
@@ -144,61 +146,113 @@ impl Default for LEAdvertisementProperties {
 
 #[zbus::proxy(interface = "org.bluez.LEAdvertisement1")]
 trait LEAdvertisement {
-    fn Release(&self) -> zbus::Result<()>;
+    fn Release(&self) -> crate::Result<()>;
 }
 
-pub async fn register_advertisement(
+/// Defines the `LEAdvertisingManager` trait for registering advertisements with an adapter.
+#[zbus::proxy(
+    default_service = "org.bluez",
+    interface = "org.bluez.LEAdvertisingManager1"
+)]
+pub trait LEAdvertisingManager {
+    /// Registers the advertisement object exported at `advertisement`.
+    fn register_advertisement(
+        &self,
+        advertisement: &ObjectPath<'_>,
+        options: HashMap<&str, Value<'_>>,
+    ) -> crate::Result<()>;
+
+    /// Unregisters a previously registered advertisement.
+    fn unregister_advertisement(&self, advertisement: &ObjectPath<'_>) -> crate::Result<()>;
+
+    /// Number of advertisement instances that can still be registered.
+    #[zbus(property)]
+    fn active_instances(&self) -> crate::Result<u8>;
+
+    /// Number of advertisement instances the controller supports.
+    #[zbus(property)]
+    fn supported_instances(&self) -> crate::Result<u8>;
+}
+
+/// Name of the interface advertisements are exported with.
+const ADVERTISEMENT_INTERFACE: &str = "org.bluez.LEAdvertisement1";
+
+/// Exports `advertisement` at `advertisement_path` and registers it with the adapter.
+///
+/// The advertisement is removed from the object server again if the adapter
+/// refuses it.
+///
+/// # Arguments
+/// * `conn` - The system bus connection.
+/// * `adapter_path` - Object path of the adapter to advertise on.
+/// * `advertisement_path` - Object path to export the advertisement at.
+/// * `advertisement` - The advertisement implementing `org.bluez.LEAdvertisement1`.
+pub async fn register_advertisement<I: Interface + 'static>(
     conn: &zbus::Connection,
-    advertisement: impl Interface + 'static,
-) -> zbus::Result<()> {
-    let adapter_path = crate::paths::get_adapter_path();
-    let adv_path = crate::paths::get_advertisement_path();
-    let bus_name = zbus::names::BusName::try_from("org.bluez")?;
-
-    // Convert the advertisement path to an ObjectPath
-    let adv_object_path = ObjectPath::try_from(adv_path.as_str())?;
-
-    // Create a proxy for the LEAdvertisingManager1 interface
-    let adapter_proxy = zbus::Proxy::new(
-        conn,
-        bus_name,
-        adapter_path,
-        "org.bluez.LEAdvertisingManager1",
-    )
-    .await?;
+    adapter_path: &str,
+    advertisement_path: &str,
+    advertisement: I,
+) -> crate::Result<()> {
+    let adv_object_path = ObjectPath::try_from(advertisement_path)?;
 
     // Register the advertisement on the D-Bus
     conn.object_server()
         .at(adv_object_path.clone(), advertisement)
         .await?;
 
+    let result = announce_advertisement(conn, adapter_path, &adv_object_path).await;
+    if result.is_err() {
+        let _ = conn
+            .object_server()
+            .remove_named(&adv_object_path, I::name())
+            .await;
+    }
+    result
+}
+
+/// Registers the advertisement already exported at `adv_object_path` with the adapter.
+pub(crate) async fn announce_advertisement(
+    conn: &zbus::Connection,
+    adapter_path: &str,
+    adv_object_path: &ObjectPath<'_>,
+) -> crate::Result<()> {
+    let manager = LEAdvertisingManagerProxy::builder(conn)
+        .path(adapter_path)?
+        .build()
+        .await?;
+
     // Prepare the options dictionary (a{sv})
     let mut options: HashMap<&str, Value> = HashMap::new();
     options.insert("Type", Value::new("peripheral")); // Example option
 
-    // Call the RegisterAdvertisement method
-    adapter_proxy
-        .call_method("RegisterAdvertisement", &(adv_object_path.clone(), options))
-        .await?;
-
-    Ok(())
+    manager
+        .register_advertisement(adv_object_path, options)
+        .await
 }
 
-pub async fn unregister_advertisement(conn: &zbus::Connection) -> zbus::Result<()> {
-    let adapter_path = crate::paths::get_adapter_path();
-    let adv_path = &crate::paths::get_advertisement_path();
-    let bus_name = zbus::names::BusName::try_from("org.bluez")?;
-
-    let adapter_proxy = zbus::Proxy::new(
-        conn,
-        bus_name,
-        adapter_path,
-        "org.bluez.LEAdvertisingManager1",
-    )
-    .await?;
-    adapter_proxy
-        .call_method("UnRegisterAdvertisement", &(adv_path))
+/// Unregisters the advertisement exported at `advertisement_path` from the adapter
+/// and removes it from the object server.
+///
+/// # Arguments
+/// * `conn` - The system bus connection.
+/// * `adapter_path` - Object path of the adapter the advertisement was registered on.
+/// * `advertisement_path` - Object path the advertisement is exported at.
+pub async fn unregister_advertisement(
+    conn: &zbus::Connection,
+    adapter_path: &str,
+    advertisement_path: &str,
+) -> crate::Result<()> {
+    let adv_object_path = ObjectPath::try_from(advertisement_path)?;
+    let manager = LEAdvertisingManagerProxy::builder(conn)
+        .path(adapter_path)?
+        .build()
         .await?;
-
-    Ok(())
+    let result = manager.unregister_advertisement(&adv_object_path).await;
+    conn.object_server()
+        .remove_named(
+            &adv_object_path,
+            zbus::names::InterfaceName::from_static_str_unchecked(ADVERTISEMENT_INTERFACE),
+        )
+        .await?;
+    result
 }
@@ -14,13 +14,11 @@
 //! Here’s how you can scan for Bluetooth devices and list them:
 //!
 //! ```no_run
-//!
 //! #[tokio::main]
-//! async fn main() -> zbus::Result<()> {
-//!     let conn = bluebus::get_system_connection().await.unwrap();
-//!     let adapter = bluebus::AdapterProxy::builder(&conn)
-//!         .path(bluebus::ADAPTER_PATH)?.build().await?;
-//!      
+//! async fn main() -> bluebus::Result<()> {
+//!     let session = bluebus::Session::new().await?;
+//!     let adapter = session.adapter().await?;
+//!
 //!     if !adapter.powered().await? {
 //!         adapter.set_powered(true).await?;
 //!     }
@@ -29,18 +27,9 @@
 //!     tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
 //!     adapter.stop_discovery().await?;
 //!
-//!     let monitor = Arc::new(bluebus::Monitor::new(conn.clone(), manager).await);
-//!     let monitor_clone = Arc::clone(&monitor);
-//!
-//!     bluebus::print_avaiable_devices(&objects);
-//!
-//!     tokio::spawn(async move {
-//!         monitor.monitor_device_added().await;
-//!     });
-//!
-//!     tokio::spawn(async move {
-//!         monitor_clone.monitor_device_removed().await;
-//!     });
+//!     for device in session.list_devices().await? {
+//!         println!("{} ({})", device.address, device.alias);
+//!     }
 //!     Ok(())
 //! }
 //! ```
@@ -53,6 +42,7 @@
 //! - [`gatt_service`] - Interfaces with Bluetooth GATT services.
 //! - [`monitor`] - Monitors Bluetooth events like device additions/removals.
 //! - [`object_manager`] - Handles D-Bus object management.
+//! - [`session`] - Binds a D-Bus connection to one adapter.
 //!
 //! ## Installation
 //! Add the following to your `Cargo.toml`:
@@ -63,38 +53,79 @@
 //! ```
 //!
 
+/// Decodes manufacturer and service data payloads.
+pub mod ad;
 /// Manages Bluetooth adapter interactions.
 pub mod adapter;
 /// Handles Bluetooth authentication agents.
 pub mod agent;
 /// Manages agent registrations.
 pub mod agent_manager;
+/// Names Bluetooth SIG assigned numbers.
+#[cfg(feature = "assigned-numbers")]
+pub mod assigned_numbers;
 /// Caches Bluetooth device states.
 pub mod cache;
+/// Decodes the Class of Device of BR/EDR devices.
+pub mod class;
 /// Handles Bluetooth connections.
 pub mod connection;
 /// Controls Bluetooth devices.
 pub mod device;
+/// Defines the crate-wide error type.
+pub mod error;
 /// Interfaces with GATT characteristics and services.
 pub mod gatt;
+/// Discovers the GATT services of remote devices.
+pub mod gatt_client;
+/// Reads and writes characteristics through acquired sockets.
+pub mod gatt_io;
 /// Manages Bluetooth Low Energy advertisements.
 pub mod leadvertisement;
 /// Listens for Bluetooth device events.
 pub mod monitor;
+/// Streams notifications of remote GATT characteristics.
+pub mod notifications;
 /// Manages D-Bus objects.
 pub mod object_manager;
 /// Defines Bluetooth system paths.
 pub mod paths;
+/// Tracks devices entering and leaving range.
+pub mod presence;
+/// Smooths RSSI readings and estimates distances.
+pub mod proximity;
+/// Scans for devices and reports their advertisements.
+pub mod scanner;
+/// Watches the BlueZ daemon joining and leaving the bus.
+pub mod service;
+/// Binds a connection to an adapter and its object paths.
+pub mod session;
+/// Parses and normalises Bluetooth UUIDs.
+pub mod uuid;
 
 // Re-export modules for easier access.
+pub use ad::*;
 pub use adapter::*;
 pub use agent::*;
 pub use agent_manager::*;
+#[cfg(feature = "assigned-numbers")]
+pub use assigned_numbers::*;
 pub use cache::*;
+pub use class::*;
 pub use connection::*;
 pub use device::*;
+pub use error::*;
 pub use gatt::*;
+pub use gatt_client::*;
+pub use gatt_io::*;
 pub use leadvertisement::*;
 pub use monitor::*;
+pub use notifications::*;
 pub use object_manager::*;
 pub use paths::*;
+pub use presence::*;
+pub use proximity::*;
+pub use scanner::*;
+pub use service::*;
+pub use session::*;
+pub use uuid::*;
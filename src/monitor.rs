@@ -1,19 +1,71 @@
-use futures::StreamExt;
-use tokio::sync::mpsc;
+use futures::{Stream, StreamExt};
 use std::collections::HashMap;
+use tokio::sync::{mpsc, watch};
+
+/// Number of events buffered per stream before internal tasks wait for the consumer.
+pub(crate) const EVENT_BUFFER: usize = 64;
+
+/// A device whose properties changed, together with the fields that changed.
+#[derive(Debug, Clone)]
+pub struct DeviceChange {
+    /// The device after the change was applied.
+    pub device: crate::cache::DeviceInfo,
+    /// The fields that changed value. With [`Monitor::with_unchanged_properties`]
+    /// enabled, also the fields whose value was signalled again unchanged.
+    pub changed: Vec<crate::cache::DeviceField>,
+}
+
+/// An event reported by [`Monitor::events`].
+#[derive(Debug, Clone)]
+pub enum DeviceEvent {
+    /// A device BlueZ already knew about when monitoring started.
+    Present(crate::cache::DeviceInfo),
+    /// A new device was discovered.
+    Added(crate::cache::DeviceInfo),
+    /// A device was removed from BlueZ.
+    Removed(crate::cache::DeviceInfo),
+    /// Properties of a known device changed.
+    Changed(DeviceChange),
+    /// `bluetoothd` left the bus. Every cached device was reported as removed
+    /// beforehand.
+    ServiceLost,
+    /// `bluetoothd` is back on the bus. The devices it exports are reported as
+    /// added afterwards.
+    ServiceBack,
+}
+
+/// Stops every task started by a [`Monitor`].
+#[derive(Clone)]
+pub struct MonitorHandle {
+    cancel: watch::Sender<bool>,
+}
+
+impl MonitorHandle {
+    /// Stops all internal tasks; the monitor's event streams end afterwards.
+    pub fn cancel(&self) {
+        self.cancel.send_replace(true);
+    }
+
+    /// Checks if the monitor was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancel.borrow()
+    }
+}
 
 /// Monitors Bluetooth device connections and disconnections.
-/// This struct listens for events related to devices being added or removed from the system.
+/// This struct listens for events related to devices and adapters being added,
+/// removed or changed.
 pub struct Monitor {
     /// Shared reference to the D-Bus connection.
     connection: std::sync::Arc<zbus::Connection>,
     /// Proxy for managing object events.
     manager: std::sync::Arc<crate::ObjectManagerProxy<'static>>,
-    device_added_tx: mpsc::Sender<crate::cache::DeviceInfo>,
-    device_removed_tx: mpsc::Sender<crate::cache::DeviceInfo>,
-    device_changed_tx: mpsc::Sender<crate::cache::DeviceInfo>,
-    /// Local device cache for monitoring
-    devices: std::sync::Arc<tokio::sync::RwLock<HashMap<String, crate::cache::DeviceInfo>>>,
+    /// Whether devices already known at startup are reported as present.
+    initial_events: bool,
+    /// Whether properties signalled with an unchanged value are reported.
+    unchanged_properties: bool,
+    /// Cancellation flag shared with every task the monitor starts.
+    cancel: watch::Sender<bool>,
 }
 
 impl Monitor {
@@ -28,235 +80,427 @@ impl Monitor {
     pub async fn new(
         connection: std::sync::Arc<zbus::Connection>,
         manager: std::sync::Arc<crate::ObjectManagerProxy<'static>>,
-        device_added_tx: mpsc::Sender<crate::cache::DeviceInfo>,
-        device_removed_tx: mpsc::Sender<crate::cache::DeviceInfo>,
-        device_changed_tx: mpsc::Sender<crate::cache::DeviceInfo>,
     ) -> Self {
         Self {
             connection,
             manager,
-            device_added_tx,
-            device_removed_tx,
-            device_changed_tx,
-            devices: std::sync::Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+            initial_events: true,
+            unchanged_properties: false,
+            cancel: watch::Sender::new(false),
         }
     }
 
-    /// Monitors the removal of Bluetooth devices.
-    /// This listens for D-Bus signals when devices are removed and logs the event.
-    pub async fn monitor_device_removed(&self) {
-        let mut interfaces_removed = match self.manager.receive_interfaces_removed().await {
-            Ok(stream) => stream,
-            Err(_) => return,
+    /// Sets whether devices BlueZ already knows about when monitoring starts are
+    /// reported as [`DeviceEvent::Present`]. Enabled by default.
+    ///
+    /// # Arguments
+    /// * `enabled` - `false` to only report devices added afterwards.
+    pub fn with_initial_events(mut self, enabled: bool) -> Self {
+        self.initial_events = enabled;
+        self
+    }
+
+    /// Sets whether [`DeviceEvent::Changed`] is also reported when BlueZ signals
+    /// a property without changing its value, e.g. when a device advertises the
+    /// same manufacturer data again. Disabled by default.
+    ///
+    /// # Arguments
+    /// * `enabled` - `true` to report every signalled property.
+    pub fn with_unchanged_properties(mut self, enabled: bool) -> Self {
+        self.unchanged_properties = enabled;
+        self
+    }
+
+    /// Returns a handle that stops every task started by this monitor.
+    pub fn handle(&self) -> MonitorHandle {
+        MonitorHandle {
+            cancel: self.cancel.clone(),
+        }
+    }
+
+    /// Creates a stream of device events.
+    ///
+    /// The stream starts with the devices BlueZ already knows about, followed by
+    /// devices being added, removed and changed. Every call creates an independent
+    /// stream with its own device cache. The stream ends once the monitor is
+    /// cancelled through its [`MonitorHandle`].
+    ///
+    /// If `bluetoothd` restarts, the cache is rebuilt: every device is reported
+    /// as removed followed by [`DeviceEvent::ServiceLost`], then
+    /// [`DeviceEvent::ServiceBack`] followed by the devices the new daemon exports.
+    pub async fn events(&self) -> crate::Result<impl Stream<Item = DeviceEvent>> {
+        let mut service_events = Box::pin(crate::service_events(&self.connection).await?);
+        let mut interfaces_added = self.manager.receive_interfaces_added().await?;
+        let mut interfaces_removed = self.manager.receive_interfaces_removed().await?;
+        let objects = self.manager.get_managed_objects().await?;
+
+        let (events_tx, events_rx) = mpsc::channel(EVENT_BUFFER);
+        let mut tracker = DeviceTracker {
+            connection: self.connection.clone(),
+            devices: std::sync::Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+            watchers: HashMap::new(),
+            events_tx,
+            unchanged_properties: self.unchanged_properties,
         };
-        let devices = self.devices.clone();
-        
-        loop {
-            if let Some(signal) = interfaces_removed.next().await {
-                if let Ok(args) = signal.args() {
-                    let path = args.object_path().as_str();
-
-                    if let Some(removed_dev) = devices.write().await.remove(path) {
-                        let _ = self.device_removed_tx.send(removed_dev).await;
+        let initial_events = self.initial_events;
+        let mut cancel = self.cancel.subscribe();
+        let consumer = tracker.events_tx.clone();
+        let manager = self.manager.clone();
+
+        tokio::spawn(async move {
+            let event = initial_events.then_some(DeviceEvent::Present as fn(_) -> _);
+            tracker.devices_found(objects, event).await;
+
+            loop {
+                tokio::select! {
+                    _ = cancelled(&mut cancel) => break,
+                    _ = consumer.closed() => break,
+                    Some(event) = service_events.next() => match event {
+                        crate::ServiceEvent::Lost => {
+                            tracker.clear().await;
+                            let _ = consumer.send(DeviceEvent::ServiceLost).await;
+                        }
+                        crate::ServiceEvent::Back => {
+                            tracker.clear().await;
+                            let _ = consumer.send(DeviceEvent::ServiceBack).await;
+                            if let Ok(objects) = manager.get_managed_objects().await {
+                                tracker.devices_found(objects, Some(DeviceEvent::Added)).await;
+                            }
+                        }
+                    },
+                    Some(signal) = interfaces_added.next() => {
+                        if let Ok(args) = signal.args() {
+                            if let Some(properties) = args.interfaces().get("org.bluez.Device1") {
+                                let path = args.object_path().clone();
+                                tracker
+                                    .device_added(path, properties, Some(DeviceEvent::Added))
+                                    .await;
+                            }
+                        }
+                    }
+                    Some(signal) = interfaces_removed.next() => {
+                        if let Ok(args) = signal.args() {
+                            let interfaces = args.interfaces();
+                            if interfaces.iter().any(|interface| interface == "org.bluez.Device1") {
+                                tracker.device_removed(args.object_path().as_str()).await;
+                            }
+                        }
                     }
+                    else => break,
                 }
             }
-        }
+
+            tracker.stop();
+        });
+
+        Ok(futures::stream::unfold(
+            events_rx,
+            |mut events_rx| async move { events_rx.recv().await.map(|event| (event, events_rx)) },
+        ))
     }
 
-    /// Monitors the addition of new Bluetooth devices.
-    /// This listens for D-Bus signals when new devices are added and logs the event.
-    pub async fn monitor_device_added(&self) {
-        let mut interfaces_added = match self.manager.receive_interfaces_added().await {
-            Ok(stream) => stream,
-            Err(_) => return,
-        };
-        let devices = self.devices.clone();
-        
-        loop {
-            if let Some(signal) = interfaces_added.next().await {
-                if let Ok(args) = signal.args() {
-                    if let Some(interfaces) = args.interfaces().get("org.bluez.Device1") {
-                        if let Some(address) = interfaces.get("Address") {
-                            let addr = address
-                                .downcast_ref::<zbus::zvariant::Str>()
-                                .map(|s| s.as_str().to_owned())
-                                .unwrap_or_default()
-                                .to_string();
-
-                            let alias = interfaces
-                                .get("Alias")
-                                .and_then(|alias| alias.downcast_ref::<zbus::zvariant::Str>().ok())
-                                .map(|s| s.as_str().to_owned())
-                                .unwrap_or_else(|| addr.to_string());
-
-                            let connected = interfaces
-                                .get("Connected")
-                                .and_then(|v| v.downcast_ref::<bool>().ok())
-                                .unwrap_or(false);
-
-                            let paired = interfaces
-                                .get("Paired")
-                                .and_then(|v| v.downcast_ref::<bool>().ok())
-                                .unwrap_or(false);
-
-                            let address_type = interfaces
-                                .get("AddressType")
-                                .and_then(|v| v.downcast_ref::<zbus::zvariant::Str>().ok())
-                                .map(|s| s.as_str().to_owned());
-
-                            let connectable = interfaces
-                                .get("Connectable")
-                                .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                            let discoverable = interfaces
-                                .get("Discoverable")
-                                .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                            let discoverable_timeout = interfaces
-                                .get("DiscoverableTimeout")
-                                .and_then(|v| v.downcast_ref::<u32>().ok());
-
-                            let discovering = interfaces
-                                .get("Discovering")
-                                .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                            let name = interfaces
-                                .get("Name")
-                                .and_then(|v| v.downcast_ref::<zbus::zvariant::Str>().ok())
-                                .map(|s| s.as_str().to_owned());
-
-                            let pairable = interfaces
-                                .get("Pairable")
-                                .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                            let pairable_timeout = interfaces
-                                .get("PairableTimeout")
-                                .and_then(|v| v.downcast_ref::<u32>().ok());
-
-                            let power_state = interfaces
-                                .get("PowerState")
-                                .and_then(|v| v.downcast_ref::<zbus::zvariant::Str>().ok())
-                                .map(|s| s.as_str().to_owned());
-
-                            let powered = interfaces
-                                .get("Powered")
-                                .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                            let trusted = interfaces
-                                .get("Trusted")
-                                .and_then(|v| v.downcast_ref::<bool>().ok());
-
-                            let rssi = interfaces
-                                .get("RSSI")
-                                .and_then(|v| v.downcast_ref::<i16>().ok());
-
-                            let path = args.object_path().to_string();
-
-                            let new_device = crate::cache::DeviceInfo {
-                                address: addr.to_string(),
-                                alias,
-                                connected,
-                                paired,
-                                address_type,
-                                connectable,
-                                discoverable,
-                                discoverable_timeout,
-                                discovering,
-                                name,
-                                pairable,
-                                pairable_timeout,
-                                power_state,
-                                powered,
-                                trusted,
-                                rssi,
-                            };
-                            
-                            devices.write().await.insert(path.clone(), new_device.clone());
-                            
-                            let _ = self.monitor_device_properties(
-                                self.connection.clone(),
-                                std::sync::Arc::new(path),
-                                devices.clone(),
-                                self.device_changed_tx.clone(),
-                            )
-                            .await;
-                            let _ = self.device_added_tx.send(new_device).await;
+    /// Creates a stream of adapter events.
+    ///
+    /// The stream starts with the adapters BlueZ already knows about, followed by
+    /// adapters being added and removed and changes to their properties, e.g.
+    /// `Powered`, `Discovering` or `PowerState`. It ends once the monitor is
+    /// cancelled through its [`MonitorHandle`].
+    ///
+    /// If `bluetoothd` restarts, every adapter is reported as removed and the
+    /// adapters exported by the new daemon as added. Use [`crate::adapter_events`]
+    /// instead when only adapters being plugged in and out are of interest.
+    pub async fn adapter_events(&self) -> crate::Result<impl Stream<Item = crate::AdapterEvent>> {
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender("org.bluez")?
+            .interface("org.freedesktop.DBus.Properties")?
+            .member("PropertiesChanged")?
+            .add_arg(crate::ADAPTER_INTERFACE)?
+            .build();
+        let mut properties_changed =
+            zbus::MessageStream::for_match_rule(rule, &self.connection, None).await?;
+        let mut service_events = Box::pin(crate::service_events(&self.connection).await?);
+        let mut interfaces_added = self.manager.receive_interfaces_added().await?;
+        let mut interfaces_removed = self.manager.receive_interfaces_removed().await?;
+        let objects = self.manager.get_managed_objects().await?;
+
+        let (events_tx, events_rx) = mpsc::channel(EVENT_BUFFER);
+        let initial_events = self.initial_events;
+        let mut cancel = self.cancel.subscribe();
+        let manager = self.manager.clone();
+
+        tokio::spawn(async move {
+            let mut adapters = HashMap::new();
+            let event = initial_events.then_some(crate::AdapterEvent::Present as fn(_) -> _);
+            adapters_found(&mut adapters, objects, &events_tx, event).await;
+
+            loop {
+                let event = tokio::select! {
+                    _ = cancelled(&mut cancel) => break,
+                    _ = events_tx.closed() => break,
+                    Some(event) = service_events.next() => {
+                        for (_, adapter) in adapters.drain() {
+                            let _ = events_tx.send(crate::AdapterEvent::Removed(adapter.path)).await;
+                        }
+                        if event == crate::ServiceEvent::Back {
+                            if let Ok(objects) = manager.get_managed_objects().await {
+                                let event = Some(crate::AdapterEvent::Added as fn(_) -> _);
+                                adapters_found(&mut adapters, objects, &events_tx, event).await;
+                            }
+                        }
+                        continue;
+                    }
+                    Some(signal) = interfaces_added.next() => {
+                        let Ok(args) = signal.args() else { continue };
+                        let path = args.object_path().clone();
+                        let Some(adapter) = args
+                            .interfaces()
+                            .get(crate::ADAPTER_INTERFACE)
+                            .and_then(|properties| crate::AdapterInfo::from_properties(path, properties))
+                        else {
+                            continue;
+                        };
+                        if adapters.insert(adapter.path.to_string(), adapter.clone()).is_some() {
+                            continue;
+                        }
+                        crate::AdapterEvent::Added(adapter)
+                    }
+                    Some(signal) = interfaces_removed.next() => {
+                        let Ok(args) = signal.args() else { continue };
+                        let interfaces = args.interfaces();
+                        if !interfaces.iter().any(|name| name == crate::ADAPTER_INTERFACE) {
+                            continue;
                         }
+                        let Some(adapter) = adapters.remove(args.object_path().as_str()) else {
+                            continue;
+                        };
+                        crate::AdapterEvent::Removed(adapter.path)
                     }
+                    Some(Ok(message)) = properties_changed.next() => {
+                        let header = message.header();
+                        let path = header.path();
+                        let Some(adapter) = path.and_then(|path| adapters.get_mut(path.as_str())) else {
+                            continue;
+                        };
+                        let signal = zbus::fdo::PropertiesChanged::from_message(message.clone());
+                        let Some(signal) = signal else { continue };
+                        let Ok(args) = signal.args() else { continue };
+                        let mut changed = adapter.apply_changes(args.changed_properties());
+                        changed.extend(adapter.invalidate(args.invalidated_properties()));
+                        if changed.is_empty() {
+                            continue;
+                        }
+                        crate::AdapterEvent::Changed(crate::AdapterChange {
+                            adapter: adapter.clone(),
+                            changed,
+                        })
+                    }
+                    else => break,
+                };
+
+                if events_tx.send(event).await.is_err() {
+                    break;
                 }
             }
+        });
+
+        Ok(futures::stream::unfold(
+            events_rx,
+            |mut events_rx| async move { events_rx.recv().await.map(|event| (event, events_rx)) },
+        ))
+    }
+}
+
+/// Caches every adapter in `objects`, reporting each one using `event`.
+async fn adapters_found(
+    adapters: &mut HashMap<String, crate::AdapterInfo>,
+    objects: crate::object_manager::ObjectsMap,
+    events_tx: &mpsc::Sender<crate::AdapterEvent>,
+    event: Option<fn(crate::AdapterInfo) -> crate::AdapterEvent>,
+) {
+    for (path, interfaces) in objects {
+        let Some(adapter) = interfaces
+            .get(crate::ADAPTER_INTERFACE)
+            .and_then(|properties| crate::AdapterInfo::from_properties(path, properties))
+        else {
+            continue;
+        };
+        if adapters
+            .insert(adapter.path.to_string(), adapter.clone())
+            .is_some()
+        {
+            continue;
+        }
+        if let Some(event) = event {
+            let _ = events_tx.send(event(adapter)).await;
         }
     }
+}
 
-    /// Monitors property changes of Bluetooth devices.
-    /// Updates internal cache when a device property (e.g., alias, connection status) changes.
-    async fn monitor_device_properties(
-        &self,
-        conn: std::sync::Arc<zbus::Connection>,
-        object_path: std::sync::Arc<String>,
-        devices: std::sync::Arc<tokio::sync::RwLock<HashMap<String, crate::cache::DeviceInfo>>>,
-        device_changed_tx: mpsc::Sender<crate::cache::DeviceInfo>,
-    ) -> zbus::Result<()> {
-        let path_clone = object_path.clone();
+/// Resolves once the monitor is cancelled; never resolves if every handle was dropped.
+async fn cancelled(cancel: &mut watch::Receiver<bool>) {
+    if cancel.wait_for(|cancelled| *cancelled).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
 
-        tokio::spawn(async move {
-            let props = match zbus::fdo::PropertiesProxy::builder(&conn)
-                .destination("org.bluez")
-                .and_then(|builder| builder.path(object_path.as_str()))
-            {
-                Ok(builder) => match builder.build().await {
-                    Ok(proxy) => proxy,
-                    Err(_) => return,
-                },
-                Err(_) => return,
-            };
+/// State of one event stream: the device cache and a property watcher per device.
+struct DeviceTracker {
+    /// Shared reference to the D-Bus connection.
+    connection: std::sync::Arc<zbus::Connection>,
+    /// Local device cache, keyed by object path.
+    devices: std::sync::Arc<tokio::sync::RwLock<HashMap<String, crate::cache::DeviceInfo>>>,
+    /// Property watcher tasks, keyed by object path.
+    watchers: HashMap<String, tokio::task::AbortHandle>,
+    /// Sender feeding the event stream.
+    events_tx: mpsc::Sender<DeviceEvent>,
+    /// Whether properties signalled with an unchanged value are reported.
+    unchanged_properties: bool,
+}
 
-            let mut props_changed = match props.receive_properties_changed().await {
-                Ok(stream) => stream,
-                Err(_) => return,
-            };
+impl DeviceTracker {
+    /// Caches a device, starts watching its properties and reports it using `event`.
+    async fn device_added(
+        &mut self,
+        path: zbus::zvariant::OwnedObjectPath,
+        properties: &HashMap<String, zbus::zvariant::OwnedValue>,
+        event: Option<fn(crate::cache::DeviceInfo) -> DeviceEvent>,
+    ) {
+        let Ok(device) = crate::cache::DeviceInfo::from_object(path.clone(), properties) else {
+            return;
+        };
 
-            while let Some(signal) = props_changed.next().await {
-                if let Ok(args) = signal.args() {
-                    let interface_name = args.interface_name().to_string();
-                    let changed_props = args.changed_properties();
-
-                    if interface_name == "org.bluez.Device1" {
-                        if let Some(mut device) = devices.read().await.get(object_path.as_str()).cloned() {
-                            let mut changed = false;
-                            
-                            if let Some(new_value) = changed_props.get("Connected") {
-                                if let Ok(val) = new_value.downcast_ref::<bool>() {
-                                    device.connected = val;
-                                    changed = true;
-                                }
-                            }
+        let key = path.to_string();
+        if self
+            .devices
+            .write()
+            .await
+            .insert(key.clone(), device.clone())
+            .is_some()
+        {
+            return;
+        }
 
-                            if let Some(new_value) = changed_props.get("Paired") {
-                                if let Ok(val) = new_value.downcast_ref::<bool>() {
-                                    device.paired = val;
-                                    changed = true;
-                                }
-                            }
+        // Subscribe before reporting the device so no change after the event is missed.
+        let props_changed = device_properties_changed(&self.connection, &key).await;
 
-                            if let Some(new_value) = changed_props.get("Alias") {
-                                if let Ok(val) = new_value.downcast_ref::<zbus::zvariant::Str>() {
-                                    device.alias = val.as_str().to_owned();
-                                    changed = true;
-                                }
-                            }
+        if let Some(event) = event {
+            let _ = self.events_tx.send(event(device)).await;
+        }
+
+        if let Ok(props_changed) = props_changed {
+            let watcher = tokio::spawn(monitor_device_properties(
+                props_changed,
+                key.clone(),
+                self.devices.clone(),
+                self.events_tx.clone(),
+                self.unchanged_properties,
+            ));
+            self.watchers.insert(key, watcher.abort_handle());
+        }
+    }
 
-                            if changed {
-                                devices.write().await.insert(path_clone.to_string(), device.clone());
-                                let _ = device_changed_tx.send(device).await;
+    /// Caches every device in `objects`, reporting each one using `event`.
+    async fn devices_found(
+        &mut self,
+        objects: crate::object_manager::ObjectsMap,
+        event: Option<fn(crate::cache::DeviceInfo) -> DeviceEvent>,
+    ) {
+        for (path, interfaces) in objects {
+            if let Some(properties) = interfaces.get("org.bluez.Device1") {
+                self.device_added(path, properties, event).await;
+            }
+        }
+    }
+
+    /// Drops a device from the cache, stops its watcher and reports the removal.
+    async fn device_removed(&mut self, path: &str) {
+        if let Some(watcher) = self.watchers.remove(path) {
+            watcher.abort();
+        }
+
+        let removed = self.devices.write().await.remove(path);
+        if let Some(device) = removed {
+            let _ = self.events_tx.send(DeviceEvent::Removed(device)).await;
+        }
+    }
+
+    /// Empties the cache, stops every watcher and reports each device as removed.
+    async fn clear(&mut self) {
+        self.stop();
+
+        let removed: Vec<_> = self.devices.write().await.drain().collect();
+        for (_, device) in removed {
+            let _ = self.events_tx.send(DeviceEvent::Removed(device)).await;
+        }
+    }
+
+    /// Stops every property watcher.
+    fn stop(&mut self) {
+        for (_, watcher) in self.watchers.drain() {
+            watcher.abort();
+        }
+    }
+}
+
+/// Subscribes to the property changes of the device at `object_path`.
+async fn device_properties_changed(
+    conn: &zbus::Connection,
+    object_path: &str,
+) -> crate::Result<zbus::fdo::PropertiesChangedStream> {
+    let props = zbus::fdo::PropertiesProxy::builder(conn)
+        .destination("org.bluez")?
+        .path(object_path)?
+        .build()
+        .await?;
+
+    Ok(props.receive_properties_changed().await?)
+}
+
+/// Monitors property changes of a Bluetooth device.
+/// Applies every changed or invalidated property to the cache and reports the
+/// fields that changed, or every signalled field if `unchanged_properties` is set.
+async fn monitor_device_properties(
+    mut props_changed: zbus::fdo::PropertiesChangedStream,
+    object_path: String,
+    devices: std::sync::Arc<tokio::sync::RwLock<HashMap<String, crate::cache::DeviceInfo>>>,
+    events_tx: mpsc::Sender<DeviceEvent>,
+    unchanged_properties: bool,
+) {
+    while let Some(signal) = props_changed.next().await {
+        if let Ok(args) = signal.args() {
+            if args.interface_name().as_str() != "org.bluez.Device1" {
+                continue;
+            }
+
+            let update = {
+                let mut devices = devices.write().await;
+                devices.get_mut(object_path.as_str()).and_then(|device| {
+                    let mut changed = device.apply_changes(args.changed_properties());
+                    if unchanged_properties {
+                        let signalled = args
+                            .changed_properties()
+                            .keys()
+                            .filter_map(|name| crate::cache::DeviceField::from_property(name));
+                        for field in signalled {
+                            if !changed.contains(&field) {
+                                changed.push(field);
                             }
                         }
                     }
+                    changed.extend(device.invalidate(args.invalidated_properties()));
+                    (!changed.is_empty()).then(|| DeviceChange {
+                        device: device.clone(),
+                        changed,
+                    })
+                })
+            };
+
+            if let Some(update) = update {
+                if events_tx.send(DeviceEvent::Changed(update)).await.is_err() {
+                    break;
                 }
             }
-        });
-
-        Ok(())
+        }
     }
 }
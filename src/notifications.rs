@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Weak};
+use std::time::SystemTime;
+
+use futures::{Stream, StreamExt};
+use tokio::sync::{broadcast, oneshot};
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::RemoteCharacteristic;
+
+/// Notification sessions of the characteristics of one [`crate::GattServices`],
+/// keyed by characteristic path.
+///
+/// The map is only locked to look up or insert the slot of a characteristic;
+/// sessions are started and stopped with just that slot locked.
+#[derive(Default)]
+pub(crate) struct NotifySessions(std::sync::Mutex<BTreeMap<String, Arc<Slot>>>);
+
+impl std::fmt::Debug for NotifySessions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotifySessions").finish_non_exhaustive()
+    }
+}
+
+/// The session of one characteristic, if one was started.
+type Slot = tokio::sync::Mutex<Option<NotifySession>>;
+
+/// A value notified or indicated by a remote characteristic.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub value: Vec<u8>,
+    /// When the value was received.
+    pub timestamp: SystemTime,
+    /// Number of notifications dropped right before this one because the
+    /// stream was not polled fast enough.
+    pub missed: u64,
+}
+
+/// A running notification session of one characteristic.
+struct NotifySession {
+    /// The subscription shared by the session's streams; dead once all were dropped.
+    subscription: Weak<Subscription>,
+    /// The task forwarding values, which calls `StopNotify` before it ends.
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl NotifySession {
+    /// Checks if the session has stopped and its task has ended.
+    fn is_finished(&self) -> bool {
+        self.subscription.strong_count() == 0 && self.task.is_finished()
+    }
+}
+
+/// Handle shared by every stream of a session; dropping the last one stops it.
+struct Subscription {
+    /// Sender the streams subscribe to.
+    values: broadcast::Sender<Notification>,
+    /// Dropped together with the subscription to stop the session's task.
+    _stop: oneshot::Sender<()>,
+}
+
+impl RemoteCharacteristic {
+    /// Creates a stream of the values the characteristic notifies or indicates.
+    ///
+    /// The first stream of a characteristic calls `StartNotify`; `StopNotify`
+    /// is called once the last one is dropped. Streams share one session if
+    /// their characteristics come from the same [`crate::GattServices`], so
+    /// keep it around instead of discovering the services again for every
+    /// stream. Notifications are enabled again whenever the device reconnects
+    /// and its services are resolved.
+    ///
+    /// A stream that falls too far behind skips the oldest values and reports
+    /// how many in [`Notification::missed`].
+    pub async fn notifications(&self) -> crate::Result<impl Stream<Item = Notification>> {
+        let slot = {
+            let mut sessions = self
+                .sessions
+                .0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            sessions.retain(|_, slot| {
+                Arc::strong_count(slot) > 1
+                    || slot.try_lock().map_or(true, |session| {
+                        session.as_ref().is_some_and(|s| !s.is_finished())
+                    })
+            });
+            sessions.entry(self.path.to_string()).or_default().clone()
+        };
+
+        let mut session = slot.lock().await;
+        let existing = session
+            .as_ref()
+            .and_then(|session| session.subscription.upgrade());
+        let subscription = match existing {
+            Some(subscription) => subscription,
+            None => {
+                if let Some(previous) = session.take() {
+                    let _ = previous.task.await;
+                }
+                let (subscription, task) = self.start_session().await?;
+                *session = Some(NotifySession {
+                    subscription: Arc::downgrade(&subscription),
+                    task,
+                });
+                subscription
+            }
+        };
+        drop(session);
+
+        let values = subscription.values.subscribe();
+        Ok(futures::stream::unfold(
+            (subscription, values),
+            |(subscription, mut values)| async move {
+                let mut missed = 0;
+                loop {
+                    match values.recv().await {
+                        Ok(mut notification) => {
+                            notification.missed = missed;
+                            return Some((notification, (subscription, values)));
+                        }
+                        Err(broadcast::error::RecvError::Lagged(count)) => missed += count,
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        ))
+    }
+
+    /// Calls `StartNotify` and spawns the task forwarding `Value` changes.
+    async fn start_session(
+        &self,
+    ) -> crate::Result<(Arc<Subscription>, tokio::task::JoinHandle<()>)> {
+        let mut proxy = self.proxy().await?;
+        let connection = &self.connection;
+        let device = device_path(&self.service)?;
+
+        let rule = properties_changed(&self.path, "org.bluez.GattCharacteristic1")?;
+        let mut values_changed =
+            zbus::MessageStream::for_match_rule(rule, connection, None).await?;
+        let rule = properties_changed(&device, "org.bluez.Device1")?;
+        let mut device_changed =
+            zbus::MessageStream::for_match_rule(rule, connection, None).await?;
+
+        match proxy.start_notify().await {
+            Ok(()) | Err(crate::Error::InProgress(_)) => {}
+            Err(error) => return Err(error),
+        }
+
+        let (values_tx, _) = broadcast::channel(crate::monitor::EVENT_BUFFER);
+        let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+        let subscription = Arc::new(Subscription {
+            values: values_tx.clone(),
+            _stop: stop_tx,
+        });
+
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut stop_rx => break,
+                    Some(Ok(message)) = values_changed.next() => {
+                        let Some(signal) = zbus::fdo::PropertiesChanged::from_message(message) else {
+                            continue;
+                        };
+                        let Ok(args) = signal.args() else { continue };
+                        let Some(value) = args.changed_properties().get("Value") else {
+                            continue;
+                        };
+                        let Ok(value) = value.try_clone().and_then(Vec::<u8>::try_from) else {
+                            continue;
+                        };
+                        let _ = values_tx.send(Notification {
+                            value,
+                            timestamp: SystemTime::now(),
+                            missed: 0,
+                        });
+                    }
+                    Some(Ok(message)) = device_changed.next() => {
+                        let Some(signal) = zbus::fdo::PropertiesChanged::from_message(message) else {
+                            continue;
+                        };
+                        let Ok(args) = signal.args() else { continue };
+                        let resolved = args
+                            .changed_properties()
+                            .get("ServicesResolved")
+                            .and_then(|value| value.downcast_ref::<bool>().ok());
+                        if resolved == Some(true) {
+                            let _ = proxy.start_notify().await;
+                        }
+                    }
+                    else => break,
+                }
+            }
+
+            let _ = proxy.stop_notify().await;
+        });
+
+        Ok((subscription, task))
+    }
+}
+
+/// Builds a rule matching `PropertiesChanged` of `interface` on the object at `path`.
+fn properties_changed(
+    path: &str,
+    interface: &'static str,
+) -> crate::Result<zbus::MatchRule<'static>> {
+    Ok(zbus::MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .sender(crate::BLUEZ_SERVICE)?
+        .path(path.to_owned())?
+        .interface("org.freedesktop.DBus.Properties")?
+        .member("PropertiesChanged")?
+        .add_arg(interface)?
+        .build())
+}
+
+/// Returns the path of the device a service at `service` belongs to.
+fn device_path(service: &OwnedObjectPath) -> crate::Result<String> {
+    service
+        .as_str()
+        .rsplit_once('/')
+        .map(|(device, _)| device.to_owned())
+        .ok_or_else(|| crate::Error::InvalidArguments(format!("invalid service path {}", service)))
+}
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use zbus::zvariant::{OwnedObjectPath, OwnedValue};
 
 /// Type alias representing a mapping of managed D-Bus objects.
-type ObjectsMap = HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>;
+pub(crate) type ObjectsMap = HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>;
 
 #[zbus::proxy(
     default_service = "org.bluez",
@@ -15,7 +15,7 @@ pub trait ObjectManager {
     ///
     /// # Returns
     /// A `Result` containing a map of object paths to their interfaces and properties.
-    fn get_managed_objects(&self) -> zbus::Result<ObjectsMap>;
+    fn get_managed_objects(&self) -> crate::Result<ObjectsMap>;
 
     /// Signal emitted when new interfaces are added to an object.
     /// It is suggested to use this function in a tokio::thread.
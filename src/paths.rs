@@ -1,52 +1,8 @@
-use std::sync::Mutex;
+/// Default object path of the Bluetooth adapter.
+pub const ADAPTER_PATH: &str = "/org/bluez/hci0";
 
-lazy_static::lazy_static! {
-    pub static ref ADAPTER_PATH:  std::sync::Arc<Mutex<String>> =
-    std::sync::Arc::new(Mutex::new(String::from("/org/bluez/hci0")));
-}
+/// Default object path a GATT application is exported at.
+pub const GATT_APPLICATION_PATH: &str = "/org/bluez/gatt_application";
 
-lazy_static::lazy_static! {
-    pub static ref GATT_APPLICATION_PATH:  std::sync::Arc<Mutex<String>> =
-    std::sync::Arc::new(Mutex::new(String::from("/org/bluez/gatt_application")));
-}
-
-lazy_static::lazy_static! {
-    pub static ref ADVERTISEMENT_PATH:  std::sync::Arc<Mutex<String>> =
-    std::sync::Arc::new(Mutex::new(String::from("/org/bluez/diagnify/adv/0")));
-}
-
-// Getter function for ADAPTER_PATH
-pub fn get_adapter_path() -> String {
-    let global_string = ADAPTER_PATH.lock().unwrap();
-    global_string.clone() // Return a copy to avoid locking issues
-}
-
-// Setter function for ADAPTER_PATH
-pub fn set_adapter_path(new_value: &str) {
-    let mut global_string = ADAPTER_PATH.lock().unwrap();
-    *global_string = new_value.to_string();
-}
-
-// Getter function for GATT_APPLICATION_PATH
-pub fn get_gatt_application_path() -> String {
-    let global_string = GATT_APPLICATION_PATH.lock().unwrap();
-    global_string.clone() // Return a copy to avoid locking issues
-}
-
-// Setter function for GATT_APPLICATION_PATH
-pub fn set_gatt_application_path(new_value: &str) {
-    let mut global_string = GATT_APPLICATION_PATH.lock().unwrap();
-    *global_string = new_value.to_string();
-}
-
-// Getter function for ADVERTISEMENT_PATH
-pub fn get_advertisement_path() -> String {
-    let global_string = ADVERTISEMENT_PATH.lock().unwrap();
-    global_string.clone() // Return a copy to avoid locking issues
-}
-
-// Setter function for ADVERTISEMENT_PATH
-pub fn set_advertisement_path(new_value: &str) {
-    let mut global_string = ADVERTISEMENT_PATH.lock().unwrap();
-    *global_string = new_value.to_string();
-}
+/// Default object path an LE advertisement is exported at.
+pub const ADVERTISEMENT_PATH: &str = "/org/bluez/diagnify/adv/0";
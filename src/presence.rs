@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::{AdvertisementReport, Scanner};
+
+/// Default time after which a device that was not heard from is considered lost.
+pub const DEFAULT_PRESENCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A device entering or leaving range, reported by a [`PresenceTracker`].
+#[derive(Debug, Clone)]
+pub enum PresenceEvent {
+    /// A device that was not present advertised; carries its advertisement.
+    DeviceFound(AdvertisementReport),
+    /// A device was not heard from within the timeout; carries its last
+    /// advertisement, whose timestamp is the time it was last seen.
+    DeviceLost(AdvertisementReport),
+}
+
+/// Tracks which devices are in range based on when they last advertised.
+///
+/// BlueZ keeps devices in its object tree for minutes after they went silent,
+/// so a device is considered lost once no advertisement was received from it
+/// for the configured timeout instead. Every advertisement BlueZ signals
+/// refreshes the last-seen time, but BlueZ may drop advertisements while
+/// scanning, so the timeout should be several times the advertising interval
+/// of the tracked devices.
+pub struct PresenceTracker {
+    /// Scanner providing the advertisements.
+    scanner: Scanner,
+    /// Time after which a silent device is considered lost.
+    timeout: Duration,
+    /// Whether expired devices that are not paired are removed from BlueZ.
+    remove_expired: bool,
+}
+
+impl PresenceTracker {
+    /// Creates a presence tracker using [`DEFAULT_PRESENCE_TIMEOUT`].
+    ///
+    /// # Arguments
+    /// * `scanner` - The scanner providing advertisements, started by [`PresenceTracker::start`].
+    pub fn new(scanner: Scanner) -> Self {
+        Self {
+            scanner,
+            timeout: DEFAULT_PRESENCE_TIMEOUT,
+            remove_expired: false,
+        }
+    }
+
+    /// Sets the time after which a device that was not heard from is considered lost.
+    ///
+    /// # Arguments
+    /// * `timeout` - The last-seen timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets whether lost devices that are not paired are removed from BlueZ
+    /// with `RemoveDevice`. Disabled by default.
+    ///
+    /// # Arguments
+    /// * `enabled` - `true` to remove expired unpaired devices.
+    pub fn with_remove_expired(mut self, enabled: bool) -> Self {
+        self.remove_expired = enabled;
+        self
+    }
+
+    /// Starts scanning and returns the stream of presence events.
+    ///
+    /// The stream ends when the scan ends; dropping it stops discovery.
+    pub async fn start(self) -> crate::Result<impl Stream<Item = PresenceEvent>> {
+        let adapter = self.scanner.adapter().clone();
+        let reports = self.scanner.start().await?;
+        let (events_tx, events_rx) = mpsc::channel(crate::monitor::EVENT_BUFFER);
+
+        tokio::spawn(track(
+            reports,
+            self.timeout,
+            self.remove_expired.then_some(adapter),
+            events_tx,
+        ));
+
+        Ok(futures::stream::unfold(
+            events_rx,
+            |mut events_rx| async move { events_rx.recv().await.map(|event| (event, events_rx)) },
+        ))
+    }
+}
+
+/// Turns advertisement reports into presence events until either side closes.
+///
+/// # Arguments
+/// * `reports` - The advertisements received.
+/// * `timeout` - Time after which a silent device is considered lost.
+/// * `remove_from` - Adapter to remove expired unpaired devices from, if any.
+/// * `events_tx` - Sender feeding the presence event stream.
+async fn track(
+    mut reports: impl Stream<Item = AdvertisementReport> + Unpin,
+    timeout: Duration,
+    remove_from: Option<crate::AdapterProxy<'static>>,
+    events_tx: mpsc::Sender<PresenceEvent>,
+) {
+    let mut present: HashMap<String, (Instant, AdvertisementReport)> = HashMap::new();
+    let mut expiry = tokio::time::interval((timeout / 4).max(Duration::from_millis(100)));
+    expiry.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = events_tx.closed() => break,
+            report = reports.next() => {
+                let Some(report) = report else { break };
+                let key = report.path.to_string();
+                if present.insert(key, (Instant::now(), report.clone())).is_some() {
+                    continue;
+                }
+                if events_tx.send(PresenceEvent::DeviceFound(report)).await.is_err() {
+                    break;
+                }
+            }
+            _ = expiry.tick() => {
+                let now = Instant::now();
+                let expired: Vec<String> = present
+                    .iter()
+                    .filter(|(_, (seen, _))| now.duration_since(*seen) >= timeout)
+                    .map(|(key, _)| key.clone())
+                    .collect();
+
+                for key in expired {
+                    let Some((_, report)) = present.remove(&key) else { continue };
+                    if let Some(adapter) = &remove_from {
+                        let _ = remove_unpaired(adapter, &report).await;
+                    }
+                    if events_tx.send(PresenceEvent::DeviceLost(report)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Removes the device `report` was received from, unless it is paired.
+async fn remove_unpaired(
+    adapter: &crate::AdapterProxy<'static>,
+    report: &AdvertisementReport,
+) -> crate::Result<()> {
+    let device = crate::DeviceProxy::builder(adapter.inner().connection())
+        .path(report.path.clone())?
+        .build()
+        .await?;
+    if device.paired().await? {
+        return Ok(());
+    }
+    adapter.remove_device(report.path.clone()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use futures::channel::mpsc::UnboundedSender;
+    use zbus::zvariant::ObjectPath;
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(4);
+
+    fn report(path: &'static str) -> AdvertisementReport {
+        AdvertisementReport {
+            path: ObjectPath::from_static_str_unchecked(path).into(),
+            address: "00:11:22:33:44:55".to_owned(),
+            rssi: Some(-60),
+            tx_power: None,
+            manufacturer_data: HashMap::new(),
+            service_data: HashMap::new(),
+            uuids: Vec::new(),
+            name: None,
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    fn tracker() -> (
+        UnboundedSender<AdvertisementReport>,
+        mpsc::Receiver<PresenceEvent>,
+    ) {
+        let (reports_tx, reports_rx) = futures::channel::mpsc::unbounded();
+        let (events_tx, events_rx) = mpsc::channel(crate::monitor::EVENT_BUFFER);
+        tokio::spawn(track(reports_rx, TIMEOUT, None, events_tx));
+        (reports_tx, events_rx)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn repeated_advertisements_keep_device_present() {
+        let (reports_tx, mut events) = tracker();
+        let path = "/org/bluez/hci0/dev_00_11_22_33_44_55";
+
+        reports_tx.unbounded_send(report(path)).unwrap();
+        assert!(matches!(
+            events.recv().await,
+            Some(PresenceEvent::DeviceFound(_))
+        ));
+
+        for _ in 0..10 {
+            tokio::time::sleep(TIMEOUT / 2).await;
+            reports_tx.unbounded_send(report(path)).unwrap();
+        }
+        let last_seen = Instant::now();
+
+        let Some(PresenceEvent::DeviceLost(lost)) = events.recv().await else {
+            panic!("expected the device to be lost");
+        };
+        assert_eq!(lost.path.as_str(), path);
+        let silent = last_seen.elapsed();
+        assert!(silent >= TIMEOUT && silent <= TIMEOUT + TIMEOUT / 4);
+
+        reports_tx.unbounded_send(report(path)).unwrap();
+        assert!(matches!(
+            events.recv().await,
+            Some(PresenceEvent::DeviceFound(_))
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn silent_devices_expire_independently() {
+        let (reports_tx, mut events) = tracker();
+        let quiet = "/org/bluez/hci0/dev_00_11_22_33_44_55";
+        let chatty = "/org/bluez/hci0/dev_66_77_88_99_AA_BB";
+
+        reports_tx.unbounded_send(report(quiet)).unwrap();
+        reports_tx.unbounded_send(report(chatty)).unwrap();
+        for _ in 0..2 {
+            assert!(matches!(
+                events.recv().await,
+                Some(PresenceEvent::DeviceFound(_))
+            ));
+        }
+
+        tokio::time::sleep(TIMEOUT / 2).await;
+        reports_tx.unbounded_send(report(chatty)).unwrap();
+
+        let Some(PresenceEvent::DeviceLost(lost)) = events.recv().await else {
+            panic!("expected a device to be lost");
+        };
+        assert_eq!(lost.path.as_str(), quiet);
+
+        let Some(PresenceEvent::DeviceLost(lost)) = events.recv().await else {
+            panic!("expected a device to be lost");
+        };
+        assert_eq!(lost.path.as_str(), chatty);
+    }
+}
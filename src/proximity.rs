@@ -0,0 +1,349 @@
+use std::collections::{HashMap, VecDeque};
+
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::{DeviceEvent, DeviceField, DeviceInfo};
+
+/// Typical difference between the transmit power and the RSSI received at 1 m, in dB.
+pub const TX_POWER_1M_LOSS: i16 = 41;
+
+/// Largest hysteresis accepted by [`DistanceEstimator::with_hysteresis`].
+pub const MAX_HYSTERESIS: f64 = 0.9;
+
+/// Smooths successive RSSI readings of one device.
+pub trait RssiFilter: Send {
+    /// Adds a reading and returns the filtered RSSI.
+    fn update(&mut self, rssi: i16) -> f64;
+
+    /// Returns the current filtered RSSI, if any reading was added.
+    fn value(&self) -> Option<f64>;
+
+    /// Forgets every reading.
+    fn reset(&mut self);
+}
+
+/// Averages the last `window` readings.
+#[derive(Debug, Clone)]
+pub struct MovingAverage {
+    /// Number of readings averaged.
+    window: usize,
+    /// The most recent readings, oldest first.
+    samples: VecDeque<i16>,
+}
+
+impl MovingAverage {
+    /// Creates a moving average over `window` readings; a window of 0 is treated as 1.
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            samples: VecDeque::new(),
+        }
+    }
+}
+
+impl RssiFilter for MovingAverage {
+    fn update(&mut self, rssi: i16) -> f64 {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(rssi);
+        self.value().unwrap_or(rssi as f64)
+    }
+
+    fn value(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let sum: f64 = self.samples.iter().map(|rssi| *rssi as f64).sum();
+        Some(sum / self.samples.len() as f64)
+    }
+
+    fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// One-dimensional Kalman filter assuming a constant RSSI disturbed by noise.
+#[derive(Debug, Clone)]
+pub struct KalmanFilter {
+    /// Variance of the actual RSSI between two readings.
+    process_noise: f64,
+    /// Variance of a single reading.
+    measurement_noise: f64,
+    /// Current estimate, `None` before the first reading.
+    estimate: Option<f64>,
+    /// Variance of the current estimate.
+    error: f64,
+}
+
+impl KalmanFilter {
+    /// Creates a Kalman filter.
+    ///
+    /// # Arguments
+    /// * `process_noise` - Variance of the actual RSSI between readings, e.g. `0.008`.
+    /// * `measurement_noise` - Variance of a single reading, e.g. `4.0`.
+    pub fn new(process_noise: f64, measurement_noise: f64) -> Self {
+        Self {
+            process_noise,
+            measurement_noise,
+            estimate: None,
+            error: 0.0,
+        }
+    }
+}
+
+impl Default for KalmanFilter {
+    fn default() -> Self {
+        Self::new(0.008, 4.0)
+    }
+}
+
+impl RssiFilter for KalmanFilter {
+    fn update(&mut self, rssi: i16) -> f64 {
+        let measurement = rssi as f64;
+        let Some(estimate) = self.estimate else {
+            self.estimate = Some(measurement);
+            self.error = self.measurement_noise;
+            return measurement;
+        };
+
+        let predicted_error = self.error + self.process_noise;
+        let gain = predicted_error / (predicted_error + self.measurement_noise);
+        let estimate = estimate + gain * (measurement - estimate);
+        self.error = (1.0 - gain) * predicted_error;
+        self.estimate = Some(estimate);
+        estimate
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.estimate
+    }
+
+    fn reset(&mut self) {
+        self.estimate = None;
+        self.error = 0.0;
+    }
+}
+
+/// How close a device is, following the iBeacon proximity classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProximityZone {
+    /// Within a few centimetres, 0.5 m by default.
+    Immediate,
+    /// Within a few metres, 3 m by default.
+    Near,
+    /// Farther away.
+    Far,
+}
+
+/// Estimates distances from RSSI with the log-distance path-loss model.
+///
+/// The RSSI expected at 1 m is either a calibrated value or derived from the
+/// `TxPower` the device advertises.
+#[derive(Debug, Clone)]
+pub struct DistanceEstimator {
+    /// Calibrated RSSI at 1 m, preferred over the advertised `TxPower`.
+    measured_power: Option<i16>,
+    /// Path-loss exponent, 2.0 in free space and up to 4.0 indoors.
+    path_loss_exponent: f64,
+    /// Upper bound of [`ProximityZone::Immediate`] in metres.
+    immediate: f64,
+    /// Upper bound of [`ProximityZone::Near`] in metres.
+    near: f64,
+    /// Fraction a distance has to cross a zone boundary by before the zone changes.
+    hysteresis: f64,
+}
+
+impl Default for DistanceEstimator {
+    fn default() -> Self {
+        Self {
+            measured_power: None,
+            path_loss_exponent: 2.0,
+            immediate: 0.5,
+            near: 3.0,
+            hysteresis: 0.2,
+        }
+    }
+}
+
+impl DistanceEstimator {
+    /// Creates an estimator using the advertised `TxPower` and free-space path loss.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses a calibrated RSSI at 1 m instead of the advertised `TxPower`.
+    ///
+    /// # Arguments
+    /// * `rssi` - The RSSI measured 1 m from the device.
+    pub fn with_measured_power(mut self, rssi: i16) -> Self {
+        self.measured_power = Some(rssi);
+        self
+    }
+
+    /// Sets the path-loss exponent of the environment.
+    ///
+    /// # Arguments
+    /// * `exponent` - 2.0 in free space, 2.5 to 4.0 indoors.
+    pub fn with_path_loss_exponent(mut self, exponent: f64) -> Self {
+        self.path_loss_exponent = exponent;
+        self
+    }
+
+    /// Sets the zone boundaries; they are swapped if `immediate` exceeds `near`.
+    ///
+    /// # Arguments
+    /// * `immediate` - Distance in metres below which a device is immediate.
+    /// * `near` - Distance in metres below which a device is near.
+    pub fn with_zones(mut self, immediate: f64, near: f64) -> Self {
+        self.immediate = immediate.min(near);
+        self.near = immediate.max(near);
+        self
+    }
+
+    /// Sets how far past a boundary a distance has to be before the zone changes.
+    ///
+    /// # Arguments
+    /// * `hysteresis` - Fraction of the boundary, e.g. `0.2` for 20 %, clamped
+    ///   between 0 and [`MAX_HYSTERESIS`].
+    pub fn with_hysteresis(mut self, hysteresis: f64) -> Self {
+        self.hysteresis = if hysteresis.is_nan() {
+            0.0
+        } else {
+            hysteresis.clamp(0.0, MAX_HYSTERESIS)
+        };
+        self
+    }
+
+    /// Estimates the distance in metres of a device received with `rssi`.
+    ///
+    /// # Arguments
+    /// * `rssi` - The received signal strength, preferably filtered.
+    /// * `tx_power` - The `TxPower` advertised by the device.
+    ///
+    /// # Returns
+    /// `None` if neither a calibrated value nor `tx_power` is available.
+    pub fn distance(&self, rssi: f64, tx_power: Option<i16>) -> Option<f64> {
+        let reference = self
+            .measured_power
+            .or_else(|| tx_power.map(|tx_power| tx_power - TX_POWER_1M_LOSS))?;
+        let exponent = (reference as f64 - rssi) / (10.0 * self.path_loss_exponent);
+        Some(10f64.powf(exponent))
+    }
+
+    /// Returns the zone a device at `distance` metres is in.
+    pub fn zone(&self, distance: f64) -> ProximityZone {
+        if distance < self.immediate {
+            ProximityZone::Immediate
+        } else if distance < self.near {
+            ProximityZone::Near
+        } else {
+            ProximityZone::Far
+        }
+    }
+
+    /// Returns the zone a device at `distance` metres is in, only leaving
+    /// `previous` once the distance is past the boundary by the hysteresis.
+    pub fn zone_from(&self, previous: ProximityZone, distance: f64) -> ProximityZone {
+        let zone = self.zone(distance);
+        if zone > previous {
+            previous.max(self.zone(distance / (1.0 + self.hysteresis)))
+        } else if zone < previous {
+            previous.min(self.zone(distance / (1.0 - self.hysteresis)))
+        } else {
+            zone
+        }
+    }
+}
+
+/// The smoothed signal strength and estimated proximity of a device.
+#[derive(Debug, Clone)]
+pub struct Proximity {
+    /// Object path of the device.
+    pub path: OwnedObjectPath,
+    pub address: String,
+    /// The filtered RSSI.
+    pub rssi: f64,
+    /// Estimated distance in metres, if it could be estimated.
+    pub distance: Option<f64>,
+    /// Proximity zone, if the distance could be estimated.
+    pub zone: Option<ProximityZone>,
+}
+
+/// Keeps one RSSI filter per device, fed by [`DeviceEvent`]s from a [`crate::Monitor`].
+pub struct RssiTracker<F> {
+    /// Filter cloned for every newly seen device.
+    template: F,
+    /// Estimator turning filtered readings into distances and zones.
+    estimator: DistanceEstimator,
+    /// Filter and last zone per device, keyed by object path.
+    devices: HashMap<String, (F, Option<ProximityZone>)>,
+}
+
+impl<F: RssiFilter + Clone> RssiTracker<F> {
+    /// Creates a tracker.
+    ///
+    /// # Arguments
+    /// * `filter` - A fresh filter, cloned for every device.
+    /// * `estimator` - The distance estimator to use.
+    pub fn new(filter: F, estimator: DistanceEstimator) -> Self {
+        Self {
+            template: filter,
+            estimator,
+            devices: HashMap::new(),
+        }
+    }
+
+    /// Feeds an event into the tracker.
+    ///
+    /// # Returns
+    /// The device's updated proximity if the event carried a new RSSI reading.
+    pub fn update(&mut self, event: &DeviceEvent) -> Option<Proximity> {
+        match event {
+            DeviceEvent::Present(device) | DeviceEvent::Added(device) => self.reading(device),
+            DeviceEvent::Changed(change) if change.changed.contains(&DeviceField::Rssi) => {
+                self.reading(&change.device)
+            }
+            DeviceEvent::Removed(device) => {
+                let path = device.path.as_ref()?;
+                self.devices.remove(path.as_str());
+                None
+            }
+            DeviceEvent::ServiceLost => {
+                self.devices.clear();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the filtered RSSI of the device at `path`.
+    pub fn rssi(&self, path: &str) -> Option<f64> {
+        self.devices.get(path)?.0.value()
+    }
+
+    /// Adds the current RSSI of `device` to its filter.
+    fn reading(&mut self, device: &DeviceInfo) -> Option<Proximity> {
+        let path = device.path.clone()?;
+        let rssi = device.rssi?;
+
+        let (filter, zone) = self
+            .devices
+            .entry(path.to_string())
+            .or_insert_with(|| (self.template.clone(), None));
+        let rssi = filter.update(rssi);
+        let distance = self.estimator.distance(rssi, device.tx_power);
+        *zone = distance.map(|distance| match *zone {
+            Some(previous) => self.estimator.zone_from(previous, distance),
+            None => self.estimator.zone(distance),
+        });
+
+        Some(Proximity {
+            path,
+            address: device.address.clone(),
+            rssi,
+            distance,
+            zone: *zone,
+        })
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+
+use futures::{Stream, StreamExt};
+use tokio::sync::mpsc;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::{DeviceEvent, DeviceField, DeviceInfo, DiscoveryFilter, Monitor, MonitorHandle};
+
+/// Fields whose signalling means a new advertisement was received.
+const ADVERTISEMENT_FIELDS: [DeviceField; 6] = [
+    DeviceField::Rssi,
+    DeviceField::TxPower,
+    DeviceField::ManufacturerData,
+    DeviceField::ServiceData,
+    DeviceField::Uuids,
+    DeviceField::Name,
+];
+
+/// Advertising data of a device, reported by a [`Scanner`] whenever BlueZ
+/// signals one of its advertising properties, even if their values did not change.
+#[derive(Debug, Clone)]
+pub struct AdvertisementReport {
+    /// Object path of the device.
+    pub path: OwnedObjectPath,
+    pub address: String,
+    pub rssi: Option<i16>,
+    pub tx_power: Option<i16>,
+    /// Manufacturer specific data keyed by company identifier.
+    pub manufacturer_data: HashMap<u16, Vec<u8>>,
+    /// Service data keyed by service UUID.
+    pub service_data: HashMap<crate::BtUuid, Vec<u8>>,
+    pub uuids: Vec<crate::BtUuid>,
+    pub name: Option<String>,
+    /// When the update was received.
+    pub timestamp: SystemTime,
+}
+
+impl AdvertisementReport {
+    /// Builds a report from the cached state of a device, timestamped now.
+    ///
+    /// # Returns
+    /// `None` if the device has no object path.
+    pub fn from_device(device: DeviceInfo) -> Option<Self> {
+        Some(Self {
+            path: device.path?,
+            address: device.address,
+            rssi: device.rssi,
+            tx_power: device.tx_power,
+            manufacturer_data: device.manufacturer_data,
+            service_data: device.service_data,
+            uuids: device.uuids,
+            name: device.name,
+            timestamp: SystemTime::now(),
+        })
+    }
+}
+
+/// Discovers nearby devices on one adapter and reports their advertisements.
+///
+/// Discovery is started by [`Scanner::start`] and stopped once the returned
+/// [`ScanStream`] is dropped or the scan duration elapsed.
+pub struct Scanner {
+    /// The adapter to scan on.
+    adapter: crate::AdapterProxy<'static>,
+    /// Monitor providing the device events, owned by the scan.
+    monitor: Monitor,
+    /// Discovery filter applied before discovery starts.
+    filter: Option<DiscoveryFilter>,
+    /// How long to scan for; unlimited if `None`.
+    duration: Option<Duration>,
+}
+
+impl Scanner {
+    /// Creates a new scanner.
+    ///
+    /// # Arguments
+    /// * `adapter` - Proxy to the adapter to scan on.
+    /// * `monitor` - A monitor dedicated to this scanner; it is cancelled when the scan ends.
+    pub fn new(adapter: crate::AdapterProxy<'static>, monitor: Monitor) -> Self {
+        Self {
+            adapter,
+            monitor,
+            filter: None,
+            duration: None,
+        }
+    }
+
+    /// Sets the discovery filter applied before discovery starts.
+    ///
+    /// # Arguments
+    /// * `filter` - Restricts the devices BlueZ reports, e.g. by UUID or RSSI.
+    pub fn with_filter(mut self, filter: DiscoveryFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Returns the proxy of the adapter scanned on.
+    pub fn adapter(&self) -> &crate::AdapterProxy<'static> {
+        &self.adapter
+    }
+
+    /// Stops the scan after `duration`; scans until dropped otherwise.
+    ///
+    /// # Arguments
+    /// * `duration` - How long to scan for.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Starts discovery and returns the stream of advertisement reports.
+    ///
+    /// Devices BlueZ remembers from earlier scans are only reported once an
+    /// advertisement from them is received. The stream ends when the scan
+    /// duration elapsed.
+    pub async fn start(self) -> crate::Result<ScanStream> {
+        let monitor = self
+            .monitor
+            .with_initial_events(false)
+            .with_unchanged_properties(true);
+        let handle = monitor.handle();
+        let mut events = Box::pin(monitor.events().await?);
+
+        if let Some(filter) = &self.filter {
+            self.adapter.set_discovery_filter(filter).await?;
+        }
+        self.adapter.start_discovery().await?;
+
+        let devices = format!("{}/", self.adapter.inner().path());
+        let deadline = self
+            .duration
+            .map(|duration| tokio::time::Instant::now() + duration);
+        let (reports_tx, reports_rx) = mpsc::channel(crate::monitor::EVENT_BUFFER);
+        let adapter = self.adapter;
+
+        tokio::spawn(async move {
+            let elapsed = async {
+                match deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => std::future::pending().await,
+                }
+            };
+            tokio::pin!(elapsed);
+
+            loop {
+                let device = tokio::select! {
+                    _ = &mut elapsed => break,
+                    _ = reports_tx.closed() => break,
+                    event = events.next() => match event {
+                        Some(DeviceEvent::Added(device)) => device,
+                        Some(DeviceEvent::Changed(change))
+                            if change.changed.iter().any(|f| ADVERTISEMENT_FIELDS.contains(f)) =>
+                        {
+                            change.device
+                        }
+                        Some(_) => continue,
+                        None => break,
+                    },
+                };
+
+                let on_adapter = device
+                    .path
+                    .as_ref()
+                    .is_some_and(|path| path.as_str().starts_with(&devices));
+                if !on_adapter {
+                    continue;
+                }
+
+                if let Some(report) = AdvertisementReport::from_device(device) {
+                    if reports_tx.send(report).await.is_err() {
+                        break;
+                    }
+                }
+            }
+
+            let _ = adapter.stop_discovery().await;
+        });
+
+        Ok(ScanStream {
+            reports: reports_rx,
+            handle,
+        })
+    }
+}
+
+/// Stream of [`AdvertisementReport`]s returned by [`Scanner::start`].
+///
+/// Dropping it stops discovery.
+pub struct ScanStream {
+    /// Reports sent by the scan task.
+    reports: mpsc::Receiver<AdvertisementReport>,
+    /// Handle cancelling the scanner's monitor.
+    handle: MonitorHandle,
+}
+
+impl Stream for ScanStream {
+    type Item = AdvertisementReport;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.reports.poll_recv(cx)
+    }
+}
+
+impl Drop for ScanStream {
+    fn drop(&mut self) {
+        self.handle.cancel();
+    }
+}
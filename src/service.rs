@@ -0,0 +1,40 @@
+use futures::{Stream, StreamExt};
+
+/// Well-known bus name owned by `bluetoothd`.
+pub const BLUEZ_SERVICE: &str = "org.bluez";
+
+/// The BlueZ daemon leaving or (re)joining the bus.
+///
+/// When `bluetoothd` restarts, every adapter, device, agent, advertisement and
+/// GATT application registration it held is dropped without further signals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServiceEvent {
+    /// `bluetoothd` released the `org.bluez` name, e.g. because it stopped or crashed.
+    Lost,
+    /// `bluetoothd` acquired the `org.bluez` name again.
+    Back,
+}
+
+/// Creates a stream reporting `bluetoothd` leaving and joining the bus.
+///
+/// The stream only reports the events; use [`crate::Session::service_events`]
+/// to also register agents, advertisements and applications again.
+///
+/// # Arguments
+/// * `connection` - The system bus connection.
+pub async fn service_events(
+    connection: &zbus::Connection,
+) -> crate::Result<impl Stream<Item = ServiceEvent>> {
+    let dbus = zbus::fdo::DBusProxy::new(connection).await?;
+    let owner_changed = dbus
+        .receive_name_owner_changed_with_args(&[(0, BLUEZ_SERVICE)])
+        .await?;
+
+    Ok(owner_changed.filter_map(|signal| async move {
+        let args = signal.args().ok()?;
+        Some(match args.new_owner().as_ref() {
+            Some(_) => ServiceEvent::Back,
+            None => ServiceEvent::Lost,
+        })
+    }))
+}
@@ -0,0 +1,444 @@
+use std::time::Duration;
+
+use futures::future::OptionFuture;
+use futures::{Stream, StreamExt};
+use tokio::sync::mpsc;
+use zbus::object_server::Interface;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::{Agent, AgentCapability, AgentRegistration, DeviceInfo, ServiceEvent};
+
+/// How long restoring a registration waits for its adapter to reappear.
+pub const RESTORE_ADAPTER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Registrations made through a [`Session`], replayed when `bluetoothd` restarts.
+#[derive(Clone, Default)]
+struct Registrations {
+    /// Agents with the capability and default flag they were registered with.
+    agents: Vec<(OwnedObjectPath, AgentCapability, bool)>,
+    /// Adapter and object path of the registered advertisement.
+    advertisement: Option<(OwnedObjectPath, OwnedObjectPath)>,
+    /// Adapter and root path of the registered GATT application.
+    application: Option<(OwnedObjectPath, OwnedObjectPath)>,
+}
+
+/// A connection to BlueZ bound to one adapter.
+///
+/// The session owns the D-Bus connection together with the adapter, GATT
+/// application and advertisement paths it operates on, so several sessions
+/// can drive different adapters from the same process.
+#[derive(Clone)]
+pub struct Session {
+    /// The system bus connection.
+    connection: zbus::Connection,
+    /// Object path of the adapter, e.g. `/org/bluez/hci0`.
+    adapter_path: OwnedObjectPath,
+    /// Object path the GATT application is exported at.
+    gatt_application_path: OwnedObjectPath,
+    /// Object path the LE advertisement is exported at.
+    advertisement_path: OwnedObjectPath,
+    /// Registrations to restore after a `bluetoothd` restart, shared between clones.
+    registrations: std::sync::Arc<std::sync::Mutex<Registrations>>,
+}
+
+impl Session {
+    /// Connects to the system bus and creates a session using the default paths.
+    ///
+    /// # Returns
+    /// A new `Session` bound to [`crate::ADAPTER_PATH`].
+    pub async fn new() -> crate::Result<Self> {
+        let connection = crate::get_system_connection().await?;
+        Self::with_connection(connection)
+    }
+
+    /// Creates a session on an existing connection using the default paths.
+    ///
+    /// # Arguments
+    /// * `connection` - The system bus connection.
+    pub fn with_connection(connection: zbus::Connection) -> crate::Result<Self> {
+        Ok(Self {
+            connection,
+            adapter_path: OwnedObjectPath::try_from(crate::ADAPTER_PATH)?,
+            gatt_application_path: OwnedObjectPath::try_from(crate::GATT_APPLICATION_PATH)?,
+            advertisement_path: OwnedObjectPath::try_from(crate::ADVERTISEMENT_PATH)?,
+            registrations: Default::default(),
+        })
+    }
+
+    /// Binds the session to another adapter.
+    ///
+    /// # Arguments
+    /// * `path` - Object path of the adapter, e.g. `/org/bluez/hci1`.
+    pub fn with_adapter_path(mut self, path: &str) -> crate::Result<Self> {
+        self.adapter_path = OwnedObjectPath::try_from(path)?;
+        Ok(self)
+    }
+
+    /// Sets the object path the GATT application is exported at.
+    ///
+    /// # Arguments
+    /// * `path` - Object path of the application root.
+    pub fn with_gatt_application_path(mut self, path: &str) -> crate::Result<Self> {
+        self.gatt_application_path = OwnedObjectPath::try_from(path)?;
+        Ok(self)
+    }
+
+    /// Sets the object path the LE advertisement is exported at.
+    ///
+    /// # Arguments
+    /// * `path` - Object path of the advertisement.
+    pub fn with_advertisement_path(mut self, path: &str) -> crate::Result<Self> {
+        self.advertisement_path = OwnedObjectPath::try_from(path)?;
+        Ok(self)
+    }
+
+    /// Returns the underlying D-Bus connection.
+    pub fn connection(&self) -> &zbus::Connection {
+        &self.connection
+    }
+
+    /// Returns the object path of the adapter.
+    pub fn adapter_path(&self) -> &OwnedObjectPath {
+        &self.adapter_path
+    }
+
+    /// Returns the object path the GATT application is exported at.
+    pub fn gatt_application_path(&self) -> &OwnedObjectPath {
+        &self.gatt_application_path
+    }
+
+    /// Returns the object path the LE advertisement is exported at.
+    pub fn advertisement_path(&self) -> &OwnedObjectPath {
+        &self.advertisement_path
+    }
+
+    /// Creates a proxy for the session's adapter.
+    pub async fn adapter(&self) -> crate::Result<crate::AdapterProxy<'static>> {
+        Ok(crate::AdapterProxy::builder(&self.connection)
+            .path(self.adapter_path.clone())?
+            .build()
+            .await?)
+    }
+
+    /// Creates a proxy for BlueZ's object manager.
+    pub async fn object_manager(&self) -> crate::Result<crate::ObjectManagerProxy<'static>> {
+        Ok(crate::ObjectManagerProxy::new(&self.connection).await?)
+    }
+
+    /// Creates a [`crate::Monitor`] reporting device events on this connection.
+    pub async fn monitor(&self) -> crate::Result<crate::Monitor> {
+        Ok(crate::Monitor::new(
+            std::sync::Arc::new(self.connection.clone()),
+            std::sync::Arc::new(self.object_manager().await?),
+        )
+        .await)
+    }
+
+    /// Creates a [`crate::Scanner`] discovering devices on the session's adapter.
+    pub async fn scanner(&self) -> crate::Result<crate::Scanner> {
+        Ok(crate::Scanner::new(
+            self.adapter().await?,
+            self.monitor().await?,
+        ))
+    }
+
+    /// Lists every adapter known to BlueZ, not only the session's one.
+    pub async fn adapters(&self) -> crate::Result<Vec<crate::AdapterInfo>> {
+        crate::list_adapters(&self.object_manager().await?).await
+    }
+
+    /// Creates a stream of adapters being added and removed.
+    pub async fn adapter_events(
+        &self,
+    ) -> crate::Result<impl futures::Stream<Item = crate::AdapterEvent>> {
+        crate::adapter_events(&self.object_manager().await?).await
+    }
+
+    /// Lists the devices known to BlueZ under the session's adapter.
+    pub async fn list_devices(&self) -> crate::Result<Vec<DeviceInfo>> {
+        crate::list_devices(&self.connection, self.adapter_path.as_str()).await
+    }
+
+    /// Exports `advertisement` at the session's advertisement path and registers it.
+    ///
+    /// # Arguments
+    /// * `advertisement` - The advertisement implementing `org.bluez.LEAdvertisement1`.
+    pub async fn register_advertisement(
+        &self,
+        advertisement: impl Interface + 'static,
+    ) -> crate::Result<()> {
+        crate::register_advertisement(
+            &self.connection,
+            self.adapter_path.as_str(),
+            self.advertisement_path.as_str(),
+            advertisement,
+        )
+        .await?;
+
+        self.registrations().advertisement =
+            Some((self.adapter_path.clone(), self.advertisement_path.clone()));
+        Ok(())
+    }
+
+    /// Unregisters the advertisement exported at the session's advertisement path
+    /// and removes it from the object server.
+    pub async fn unregister_advertisement(&self) -> crate::Result<()> {
+        self.registrations().advertisement = None;
+        crate::unregister_advertisement(
+            &self.connection,
+            self.adapter_path.as_str(),
+            self.advertisement_path.as_str(),
+        )
+        .await
+    }
+
+    /// Exports a GATT service at `path`, usually below the GATT application path.
+    ///
+    /// # Arguments
+    /// * `path` - Object path of the service.
+    /// * `service` - The service implementing `org.bluez.GattService1`.
+    pub async fn register_service(
+        &self,
+        path: &str,
+        service: impl Interface + 'static,
+    ) -> crate::Result<()> {
+        crate::register_service(&self.connection, path, service).await
+    }
+
+    /// Exports a GATT characteristic at `path`, usually below its service.
+    ///
+    /// # Arguments
+    /// * `path` - Object path of the characteristic.
+    /// * `characteristic` - The characteristic implementing `org.bluez.GattCharacteristic1`.
+    pub async fn register_characteristic(
+        &self,
+        path: &str,
+        characteristic: impl Interface + 'static,
+    ) -> crate::Result<()> {
+        crate::register_characteristic(&self.connection, path, characteristic).await
+    }
+
+    /// Exports a GATT descriptor at `path`, usually below its characteristic.
+    ///
+    /// # Arguments
+    /// * `path` - Object path of the descriptor.
+    /// * `descriptor` - The descriptor implementing `org.bluez.GattDescriptor1`.
+    pub async fn register_descriptor(
+        &self,
+        path: &str,
+        descriptor: impl Interface + 'static,
+    ) -> crate::Result<()> {
+        crate::register_descriptor(&self.connection, path, descriptor).await
+    }
+
+    /// Registers the GATT application rooted at the session's application path.
+    pub async fn register_application(&self) -> crate::Result<()> {
+        crate::register_application(
+            &self.connection,
+            self.adapter_path.as_str(),
+            self.gatt_application_path.as_str(),
+        )
+        .await?;
+
+        self.registrations().application = Some((
+            self.adapter_path.clone(),
+            self.gatt_application_path.clone(),
+        ));
+        Ok(())
+    }
+
+    /// Unregisters the GATT application rooted at the session's application path.
+    pub async fn unregister_application(&self) -> crate::Result<()> {
+        self.registrations().application = None;
+        crate::unregister_application(
+            &self.connection,
+            self.adapter_path.as_str(),
+            self.gatt_application_path.as_str(),
+        )
+        .await
+    }
+
+    /// Serves `agent` at `path` and registers it with BlueZ.
+    ///
+    /// # Arguments
+    /// * `path` - The object path to export the agent at.
+    /// * `agent` - The agent answering `org.bluez.Agent1` requests.
+    /// * `capability` - The IO capability to register with.
+    /// * `default` - Whether to also request to become the default agent.
+    pub async fn register_agent(
+        &self,
+        path: &str,
+        agent: Agent,
+        capability: AgentCapability,
+        default: bool,
+    ) -> crate::Result<AgentRegistration> {
+        let registration =
+            crate::register_agent(&self.connection, path, agent, capability, default).await?;
+
+        let mut registrations = self.registrations();
+        registrations
+            .agents
+            .retain(|(agent, _, _)| agent != registration.path());
+        registrations
+            .agents
+            .push((registration.path().clone(), capability, default));
+        Ok(registration)
+    }
+
+    /// Creates a stream reporting `bluetoothd` leaving and joining the bus.
+    ///
+    /// Whenever `bluetoothd` comes back, the agents, advertisement and GATT
+    /// application registered through this session (or a clone of it) are
+    /// registered again before [`ServiceEvent::Back`] is reported; every
+    /// registration that could not be restored is reported as an error before
+    /// it. If `bluetoothd` leaves again in the meantime, restoring is abandoned
+    /// and [`ServiceEvent::Lost`] is reported right away.
+    ///
+    /// Registrations are only restored while this stream is being consumed;
+    /// [`crate::service_events`] and the streams of a [`crate::Monitor`] do not
+    /// restore anything.
+    pub async fn service_events(
+        &self,
+    ) -> crate::Result<impl Stream<Item = crate::Result<ServiceEvent>>> {
+        let mut service_events = Box::pin(crate::service_events(&self.connection).await?);
+        let (events_tx, events_rx) = mpsc::channel(1);
+        let session = self.clone();
+
+        tokio::spawn(async move {
+            let mut restore = None;
+
+            loop {
+                let events = tokio::select! {
+                    _ = events_tx.closed() => break,
+                    Some(errors) = OptionFuture::from(restore.as_mut()) => {
+                        restore = None;
+                        let errors: Vec<crate::Error> = errors;
+                        errors
+                            .into_iter()
+                            .map(Err)
+                            .chain([Ok(ServiceEvent::Back)])
+                            .collect()
+                    }
+                    event = service_events.next() => match event {
+                        Some(ServiceEvent::Lost) => {
+                            restore = None;
+                            vec![Ok(ServiceEvent::Lost)]
+                        }
+                        Some(ServiceEvent::Back) => {
+                            restore = Some(Box::pin(session.restore_registrations()));
+                            continue;
+                        }
+                        None => break,
+                    },
+                };
+
+                for event in events {
+                    if events_tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(futures::stream::unfold(
+            events_rx,
+            |mut events_rx| async move { events_rx.recv().await.map(|event| (event, events_rx)) },
+        ))
+    }
+
+    /// Registers everything registered through this session with BlueZ again,
+    /// e.g. after `bluetoothd` restarted.
+    ///
+    /// Every registration is restored independently of the others. Agents that
+    /// were unregistered or dropped in the meantime are skipped. Advertisements
+    /// and applications are registered once their adapter shows up, waiting at
+    /// most [`RESTORE_ADAPTER_TIMEOUT`] for it.
+    ///
+    /// # Returns
+    /// The errors of the registrations that could not be restored.
+    pub async fn restore_registrations(&self) -> Vec<crate::Error> {
+        let registrations = self.registrations().clone();
+        let mut errors = Vec::new();
+
+        for (path, capability, default) in registrations.agents {
+            let served = self
+                .connection
+                .object_server()
+                .interface::<_, Agent>(path.as_ref())
+                .await
+                .is_ok();
+            if !served {
+                self.registrations()
+                    .agents
+                    .retain(|(agent, _, _)| *agent != path);
+                continue;
+            }
+            let result =
+                crate::agent_manager::announce_agent(&self.connection, &path, capability, default)
+                    .await;
+            errors.extend(result.err());
+        }
+
+        if let Some((adapter, advertisement)) = registrations.advertisement {
+            let result = async {
+                self.wait_for_adapter(&adapter).await?;
+                crate::leadvertisement::announce_advertisement(
+                    &self.connection,
+                    adapter.as_str(),
+                    &advertisement,
+                )
+                .await
+            };
+            errors.extend(result.await.err());
+        }
+
+        if let Some((adapter, application)) = registrations.application {
+            let result = async {
+                self.wait_for_adapter(&adapter).await?;
+                crate::gatt::announce_application(&self.connection, adapter.as_str(), &application)
+                    .await
+            };
+            errors.extend(result.await.err());
+        }
+
+        errors
+    }
+
+    /// Resolves once BlueZ exports the adapter at `path`, or fails after [`RESTORE_ADAPTER_TIMEOUT`].
+    async fn wait_for_adapter(&self, path: &OwnedObjectPath) -> crate::Result<()> {
+        let manager = self.object_manager().await?;
+        let mut interfaces_added = manager.receive_interfaces_added().await?;
+        let not_found = || crate::Error::DoesNotExist(format!("adapter {} did not appear", path));
+
+        let objects = manager.get_managed_objects().await?;
+        if objects
+            .get(path)
+            .is_some_and(|interfaces| interfaces.contains_key(crate::ADAPTER_INTERFACE))
+        {
+            return Ok(());
+        }
+
+        let appeared = async {
+            while let Some(signal) = interfaces_added.next().await {
+                let args = signal.args()?;
+                if args.object_path() == path
+                    && args.interfaces().contains_key(crate::ADAPTER_INTERFACE)
+                {
+                    return Ok(());
+                }
+            }
+            Err(not_found())
+        };
+
+        tokio::time::timeout(RESTORE_ADAPTER_TIMEOUT, appeared)
+            .await
+            .unwrap_or_else(|_| Err(not_found()))
+    }
+
+    /// Locks the registrations shared between clones of this session.
+    fn registrations(&self) -> std::sync::MutexGuard<'_, Registrations> {
+        self.registrations
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
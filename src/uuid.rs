@@ -0,0 +1,213 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zbus::zvariant::{self, OwnedValue, Signature, Type, Value};
+
+/// The Bluetooth base UUID `00000000-0000-1000-8000-00805f9b34fb` that 16- and
+/// 32-bit UUIDs are shorthands of.
+pub const BLUETOOTH_BASE_UUID: u128 = 0x0000_0000_0000_1000_8000_0080_5f9b_34fb;
+
+/// Mask of the bits a 16- or 32-bit UUID replaces in the base UUID.
+const SHORT_MASK: u128 = 0xffff_ffff << 96;
+
+/// A Bluetooth UUID.
+///
+/// 16- and 32-bit UUIDs are stored expanded against [`BLUETOOTH_BASE_UUID`],
+/// so `180d`, `0000180d` and `0000180d-0000-1000-8000-00805f9b34fb` are equal.
+/// It is displayed in the shortest form and sent over D-Bus in the full form.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BtUuid(u128);
+
+impl BtUuid {
+    /// Expands a 16-bit UUID, e.g. `0x180d` for the Heart Rate service.
+    pub const fn from_u16(uuid: u16) -> Self {
+        Self::from_u32(uuid as u32)
+    }
+
+    /// Expands a 32-bit UUID.
+    pub const fn from_u32(uuid: u32) -> Self {
+        Self(BLUETOOTH_BASE_UUID | ((uuid as u128) << 96))
+    }
+
+    /// Wraps a 128-bit UUID.
+    pub const fn from_u128(uuid: u128) -> Self {
+        Self(uuid)
+    }
+
+    /// Returns the 128-bit value.
+    pub const fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// Returns the 16-bit form, if the UUID has one.
+    pub fn as_u16(&self) -> Option<u16> {
+        self.as_u32().and_then(|uuid| u16::try_from(uuid).ok())
+    }
+
+    /// Returns the 32-bit form, if the UUID has one.
+    pub fn as_u32(&self) -> Option<u32> {
+        (self.0 & !SHORT_MASK == BLUETOOTH_BASE_UUID).then_some((self.0 >> 96) as u32)
+    }
+
+    /// Returns the full hyphenated form, e.g. `0000180d-0000-1000-8000-00805f9b34fb`.
+    pub fn to_full_string(&self) -> String {
+        let hex = format!("{:032x}", self.0);
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+
+    /// Returns the assigned name of the service, characteristic or descriptor
+    /// this UUID identifies, e.g. "Heart Rate" for `180d`.
+    #[cfg(feature = "assigned-numbers")]
+    pub fn name(&self) -> Option<&'static str> {
+        crate::assigned_numbers::service_name(self)
+            .or_else(|| crate::assigned_numbers::characteristic_name(self))
+            .or_else(|| crate::assigned_numbers::descriptor_name(self))
+    }
+}
+
+impl fmt::Display for BtUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(uuid) = self.as_u16() {
+            write!(f, "{:04x}", uuid)
+        } else if let Some(uuid) = self.as_u32() {
+            write!(f, "{:08x}", uuid)
+        } else {
+            f.write_str(&self.to_full_string())
+        }
+    }
+}
+
+impl fmt::Debug for BtUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BtUuid({})", self)
+    }
+}
+
+impl FromStr for BtUuid {
+    type Err = crate::Error;
+
+    /// Parses a 16-bit (`180d`), 32-bit (`0000180d`) or 128-bit UUID, with or
+    /// without hyphens and an optional `0x` prefix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || crate::Error::InvalidArguments(format!("invalid UUID {:?}", s));
+
+        let hex = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        let hex = if hex.len() == 36 {
+            let hyphens = [8, 13, 18, 23];
+            if !hyphens.iter().all(|&i| hex.as_bytes()[i] == b'-') {
+                return Err(invalid());
+            }
+            hex.replace('-', "")
+        } else {
+            hex.to_string()
+        };
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let value = u128::from_str_radix(&hex, 16).map_err(|_| invalid())?;
+        match hex.len() {
+            4 | 8 => Ok(Self::from_u32(value as u32)),
+            32 => Ok(Self(value)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl From<u16> for BtUuid {
+    fn from(uuid: u16) -> Self {
+        Self::from_u16(uuid)
+    }
+}
+
+impl From<u32> for BtUuid {
+    fn from(uuid: u32) -> Self {
+        Self::from_u32(uuid)
+    }
+}
+
+impl From<u128> for BtUuid {
+    fn from(uuid: u128) -> Self {
+        Self::from_u128(uuid)
+    }
+}
+
+impl TryFrom<&str> for BtUuid {
+    type Error = crate::Error;
+
+    fn try_from(uuid: &str) -> Result<Self, Self::Error> {
+        uuid.parse()
+    }
+}
+
+impl TryFrom<String> for BtUuid {
+    type Error = crate::Error;
+
+    fn try_from(uuid: String) -> Result<Self, Self::Error> {
+        uuid.parse()
+    }
+}
+
+impl From<BtUuid> for String {
+    fn from(uuid: BtUuid) -> Self {
+        uuid.to_full_string()
+    }
+}
+
+impl Serialize for BtUuid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_full_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for BtUuid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let uuid = String::deserialize(deserializer)?;
+        uuid.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Type for BtUuid {
+    const SIGNATURE: &'static Signature = &Signature::Str;
+}
+
+impl zvariant::Basic for BtUuid {
+    const SIGNATURE_CHAR: char = 's';
+    const SIGNATURE_STR: &'static str = "s";
+}
+
+impl TryFrom<Value<'_>> for BtUuid {
+    type Error = zvariant::Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        let uuid: zvariant::Str<'_> = value.try_into()?;
+        uuid.as_str()
+            .parse()
+            .map_err(|error: crate::Error| zvariant::Error::Message(error.to_string()))
+    }
+}
+
+impl TryFrom<OwnedValue> for BtUuid {
+    type Error = zvariant::Error;
+
+    fn try_from(value: OwnedValue) -> Result<Self, Self::Error> {
+        Value::from(value).try_into()
+    }
+}
+
+impl From<BtUuid> for Value<'static> {
+    fn from(uuid: BtUuid) -> Self {
+        Value::from(uuid.to_full_string())
+    }
+}
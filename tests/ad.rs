@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use bluebus::{
+    AdParser, AdPayload, AdvertisementReport, AltBeacon, ContinuityMessage, ContinuityType,
+    Eddystone, EddystoneTlm, IBeacon, MicrosoftCdp, Ruuvi, APPLE_COMPANY_ID,
+    EDDYSTONE_SERVICE_UUID, RUUVI_COMPANY_ID,
+};
+
+fn hex(data: &str) -> Vec<u8> {
+    (0..data.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&data[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn close(value: Option<f64>, expected: f64) -> bool {
+    value.is_some_and(|value| (value - expected).abs() < 1e-9)
+}
+
+#[test]
+fn ibeacon() {
+    let data = hex("0215f7826da64fa24e988024bc5b71e0893e00010002c5");
+    let beacon = IBeacon::parse(&data).unwrap();
+    assert_eq!(
+        beacon.uuid.to_full_string(),
+        "f7826da6-4fa2-4e98-8024-bc5b71e0893e"
+    );
+    assert_eq!(beacon.major, 1);
+    assert_eq!(beacon.minor, 2);
+    assert_eq!(beacon.measured_power, -59);
+
+    assert!(IBeacon::parse(&data[..22]).is_none());
+}
+
+#[test]
+fn altbeacon() {
+    let data = hex("beac00112233445566778899aabbccddeeff00010002c500");
+    let beacon = AltBeacon::parse(0x0118, &data).unwrap();
+    assert_eq!(beacon.company_id, 0x0118);
+    assert_eq!(beacon.beacon_id[..4], [0x00, 0x11, 0x22, 0x33]);
+    assert_eq!(beacon.beacon_id[16..], [0x00, 0x01, 0x00, 0x02]);
+    assert_eq!(beacon.reference_rssi, -59);
+    assert_eq!(beacon.manufacturer_reserved, 0);
+}
+
+#[test]
+fn eddystone_uid() {
+    let data = hex("00e700112233445566778899aabbccddeeff0000");
+    let Some(Eddystone::Uid(uid)) = Eddystone::parse(&data) else {
+        panic!("not a UID frame");
+    };
+    assert_eq!(uid.tx_power, -25);
+    assert_eq!(
+        uid.namespace,
+        [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99]
+    );
+    assert_eq!(uid.instance, [0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]);
+}
+
+#[test]
+fn eddystone_url() {
+    let mut data = hex("10eb03");
+    data.extend_from_slice(b"goo.gl/S6zT6P");
+    let Some(Eddystone::Url(url)) = Eddystone::parse(&data) else {
+        panic!("not a URL frame");
+    };
+    assert_eq!(url.tx_power, -21);
+    assert_eq!(url.url, "https://goo.gl/S6zT6P");
+
+    let mut data = hex("100000");
+    data.extend_from_slice(b"example");
+    data.extend_from_slice(&[0x00, b'a']);
+    let Some(Eddystone::Url(url)) = Eddystone::parse(&data) else {
+        panic!("not a URL frame");
+    };
+    assert_eq!(url.url, "http://www.example.com/a");
+
+    assert!(Eddystone::parse(&hex("10eb04")).is_none());
+    assert!(Eddystone::parse(&hex("10eb0320")).is_none());
+}
+
+#[test]
+fn eddystone_tlm() {
+    let data = hex("20000bb817800000000a00000064");
+    let Some(Eddystone::Tlm(EddystoneTlm::Plain {
+        battery,
+        temperature,
+        advertisement_count,
+        uptime,
+    })) = Eddystone::parse(&data)
+    else {
+        panic!("not a plain TLM frame");
+    };
+    assert_eq!(battery, Some(3000));
+    assert!(close(temperature, 23.5));
+    assert_eq!(advertisement_count, 10);
+    assert_eq!(uptime, Duration::from_secs(10));
+
+    let data = hex("2000000080000000000000000000");
+    let Some(Eddystone::Tlm(EddystoneTlm::Plain {
+        battery,
+        temperature,
+        ..
+    })) = Eddystone::parse(&data)
+    else {
+        panic!("not a plain TLM frame");
+    };
+    assert_eq!(battery, None);
+    assert_eq!(temperature, None);
+
+    let data = hex("2001000102030405060708090a0b12345678");
+    assert_eq!(
+        Eddystone::parse(&data),
+        Some(Eddystone::Tlm(EddystoneTlm::Encrypted {
+            data: [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b],
+            salt: 0x1234,
+            integrity_check: 0x5678,
+        }))
+    );
+}
+
+#[test]
+fn eddystone_eid() {
+    let data = hex("30f00102030405060708");
+    let Some(Eddystone::Eid(eid)) = Eddystone::parse(&data) else {
+        panic!("not an EID frame");
+    };
+    assert_eq!(eid.tx_power, -16);
+    assert_eq!(eid.ephemeral_id, [1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn microsoft_cdp() {
+    let data = hex("01092000a1b2c3d400112233445566778899aabbccddeeff");
+    let cdp = MicrosoftCdp::parse(&data).unwrap();
+    assert_eq!(cdp.scenario_type, 1);
+    assert_eq!(cdp.version, 0);
+    assert_eq!(cdp.device_type, 9);
+    assert_eq!(cdp.flags, 0x20);
+    assert_eq!(cdp.salt, [0xa1, 0xb2, 0xc3, 0xd4]);
+    assert_eq!(cdp.device_hash.len(), 16);
+
+    assert!(MicrosoftCdp::parse(&data[..7]).is_none());
+    assert!(MicrosoftCdp::parse(&hex("02092000a1b2c3d4")).is_none());
+}
+
+#[test]
+fn apple_continuity() {
+    let data = hex("1005011c1a4a5f0c020e00");
+    let messages = ContinuityMessage::parse_all(&data).unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].kind, ContinuityType::NearbyInfo);
+    assert_eq!(messages[0].data, hex("011c1a4a5f"));
+    assert_eq!(messages[1].kind, ContinuityType::Handoff);
+    assert_eq!(messages[1].data, hex("0e00"));
+
+    assert!(ContinuityMessage::parse_all(&hex("100501")).is_none());
+    assert!(ContinuityMessage::parse_all(&[]).is_none());
+}
+
+#[test]
+fn ruuvi_format_3() {
+    let ruuvi = Ruuvi::parse(&hex("03291a1ece1efc18f94202ca0b53")).unwrap();
+    assert_eq!(ruuvi.format, 3);
+    assert!(close(ruuvi.humidity, 20.5));
+    assert!(close(ruuvi.temperature, 26.3));
+    assert_eq!(ruuvi.pressure, Some(102_766));
+    assert_eq!(ruuvi.acceleration, Some([-1000, -1726, 714]));
+    assert_eq!(ruuvi.battery, Some(2899));
+    assert_eq!(ruuvi.mac, None);
+
+    let ruuvi = Ruuvi::parse(&hex("03ff9a63ce1efc18f94202ca0b53")).unwrap();
+    assert!(close(ruuvi.temperature, -26.99));
+}
+
+#[test]
+fn ruuvi_format_5() {
+    let ruuvi = Ruuvi::parse(&hex("0512fc5394c37c0004fffc040cac364200cdcbb8334c884f")).unwrap();
+    assert_eq!(ruuvi.format, 5);
+    assert!(close(ruuvi.temperature, 24.3));
+    assert!(close(ruuvi.humidity, 53.49));
+    assert_eq!(ruuvi.pressure, Some(100_044));
+    assert_eq!(ruuvi.acceleration, Some([4, -4, 1036]));
+    assert_eq!(ruuvi.battery, Some(2977));
+    assert_eq!(ruuvi.tx_power, Some(4));
+    assert_eq!(ruuvi.movement_counter, Some(66));
+    assert_eq!(ruuvi.measurement_sequence, Some(205));
+    assert_eq!(ruuvi.mac, Some([0xcb, 0xb8, 0x33, 0x4c, 0x88, 0x4f]));
+
+    let invalid = Ruuvi::parse(&hex("058000ffffffff800080008000ffffffffffffffffffffffff")).unwrap();
+    assert_eq!(invalid.temperature, None);
+    assert_eq!(invalid.humidity, None);
+    assert_eq!(invalid.pressure, None);
+    assert_eq!(invalid.acceleration, None);
+    assert_eq!(invalid.battery, None);
+    assert_eq!(invalid.tx_power, None);
+    assert_eq!(invalid.movement_counter, None);
+    assert_eq!(invalid.measurement_sequence, None);
+    assert_eq!(invalid.mac, None);
+
+    assert!(Ruuvi::parse(&hex("0512fc")).is_none());
+}
+
+#[test]
+fn parser_dispatches_by_company_and_service() {
+    let parser = AdParser::new();
+
+    let payload = parser.parse_manufacturer_data(
+        APPLE_COMPANY_ID,
+        &hex("0215f7826da64fa24e988024bc5b71e0893e00010002c5"),
+    );
+    assert!(matches!(payload, Some(AdPayload::IBeacon(_))));
+
+    let payload = parser.parse_manufacturer_data(APPLE_COMPANY_ID, &hex("1005011c1a4a5f"));
+    assert!(matches!(payload, Some(AdPayload::AppleContinuity(_))));
+
+    let payload = parser.parse_manufacturer_data(
+        0x0118,
+        &hex("beac00112233445566778899aabbccddeeff00010002c500"),
+    );
+    assert!(matches!(payload, Some(AdPayload::AltBeacon(_))));
+
+    assert!(parser
+        .parse_manufacturer_data(0x0118, &hex("0102"))
+        .is_none());
+
+    let payload = parser.parse_service_data(&"FEAA".parse().unwrap(), &hex("30f00102030405060708"));
+    assert!(matches!(
+        payload,
+        Some(AdPayload::Eddystone(Eddystone::Eid(_)))
+    ));
+}
+
+#[test]
+fn parser_uses_registered_decoders() {
+    #[derive(Debug, PartialEq)]
+    struct Counter(u16);
+
+    let mut parser = AdParser::new();
+    parser.register(0xffff, |data: &[u8]| {
+        Some(Counter(u16::from_le_bytes(data.try_into().ok()?)))
+    });
+    parser.register(RUUVI_COMPANY_ID, |data: &[u8]| {
+        (data == [0xff]).then_some(())
+    });
+
+    let Some(AdPayload::Custom(payload)) = parser.parse_manufacturer_data(0xffff, &[0x34, 0x12])
+    else {
+        panic!("not decoded by the custom decoder");
+    };
+    assert_eq!(payload.company_id, 0xffff);
+    assert_eq!(payload.downcast_ref::<Counter>(), Some(&Counter(0x1234)));
+    assert_eq!(payload.downcast_ref::<u16>(), None);
+
+    assert!(parser.parse_manufacturer_data(0xffff, &[0x34]).is_none());
+
+    let payload = parser.parse_manufacturer_data(RUUVI_COMPANY_ID, &[0xff]);
+    assert!(matches!(payload, Some(AdPayload::Custom(_))));
+    let payload =
+        parser.parse_manufacturer_data(RUUVI_COMPANY_ID, &hex("03291a1ece1efc18f94202ca0b53"));
+    assert!(matches!(payload, Some(AdPayload::Ruuvi(_))));
+}
+
+#[test]
+fn parser_decodes_reports() {
+    let report = AdvertisementReport {
+        path: "/org/bluez/hci0/dev_00_11_22_33_44_55".try_into().unwrap(),
+        address: "00:11:22:33:44:55".into(),
+        rssi: Some(-60),
+        tx_power: None,
+        manufacturer_data: HashMap::from([(
+            RUUVI_COMPANY_ID,
+            hex("0512fc5394c37c0004fffc040cac364200cdcbb8334c884f"),
+        )]),
+        service_data: HashMap::from([(
+            EDDYSTONE_SERVICE_UUID,
+            hex("00e700112233445566778899aabbccddeeff0000"),
+        )]),
+        uuids: Vec::new(),
+        name: None,
+        timestamp: SystemTime::now(),
+    };
+
+    let payloads = AdParser::new().parse_report(&report);
+    assert_eq!(payloads.len(), 2);
+    assert!(matches!(payloads[0], AdPayload::Ruuvi(_)));
+    assert!(matches!(
+        payloads[1],
+        AdPayload::Eddystone(Eddystone::Uid(_))
+    ));
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use bluebus::{AdapterField, AdapterInfo};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+fn adapter() -> AdapterInfo {
+    let properties: HashMap<String, OwnedValue> = [
+        ("Address", Value::from("00:11:22:33:44:55")),
+        ("Name", Value::from("hci0")),
+        ("Alias", Value::from("Desk")),
+        ("Powered", Value::from(true)),
+        ("Pairable", Value::from(false)),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_owned(), value.try_into().unwrap()))
+    .collect();
+    let path = OwnedObjectPath::from(ObjectPath::try_from("/org/bluez/hci0").unwrap());
+
+    AdapterInfo::from_properties(path, &properties).unwrap()
+}
+
+#[test]
+fn from_properties() {
+    let adapter = adapter();
+    assert_eq!(adapter.address, "00:11:22:33:44:55");
+    assert_eq!(adapter.alias.as_deref(), Some("Desk"));
+    assert_eq!(adapter.address_type, None);
+    assert!(adapter.powered);
+    assert!(!adapter.discovering);
+
+    let path = OwnedObjectPath::from(ObjectPath::try_from("/org/bluez/hci1").unwrap());
+    assert!(AdapterInfo::from_properties(path, &HashMap::new()).is_none());
+}
+
+#[test]
+fn apply_changes() {
+    let mut adapter = adapter();
+    let changed = HashMap::from([
+        ("Alias", Value::from("Kitchen")),
+        ("Discovering", Value::from(true)),
+        ("PowerState", Value::from("on")),
+        ("Roles", Value::from(vec!["central"])),
+    ]);
+
+    let mut fields = adapter.apply_changes(&changed);
+    fields.sort_by_key(|field| field.property_name());
+    assert_eq!(
+        fields,
+        [
+            AdapterField::Alias,
+            AdapterField::Discovering,
+            AdapterField::PowerState
+        ]
+    );
+    assert_eq!(adapter.alias.as_deref(), Some("Kitchen"));
+    assert!(adapter.discovering);
+    assert_eq!(adapter.power_state.as_deref(), Some("on"));
+}
+
+#[test]
+fn apply_unchanged_or_mistyped() {
+    let mut adapter = adapter();
+    let changed = HashMap::from([
+        ("Name", Value::from("hci0")),
+        ("Powered", Value::from(true)),
+        ("Alias", Value::from(1u8)),
+        ("Pairable", Value::from("yes")),
+        ("Address", Value::from(0u32)),
+    ]);
+
+    assert!(adapter.apply_changes(&changed).is_empty());
+    assert_eq!(adapter.address, "00:11:22:33:44:55");
+    assert_eq!(adapter.alias.as_deref(), Some("Desk"));
+    assert!(!adapter.pairable);
+}
+
+#[test]
+fn invalidate() {
+    let mut adapter = adapter();
+
+    let mut fields = adapter.invalidate(&["Address", "Alias", "Powered", "Pairable", "Roles"]);
+    fields.sort_by_key(|field| field.property_name());
+    assert_eq!(fields, [AdapterField::Alias, AdapterField::Powered]);
+    assert_eq!(adapter.address, "00:11:22:33:44:55");
+    assert_eq!(adapter.alias, None);
+    assert!(!adapter.powered);
+
+    assert!(adapter.invalidate(&["Alias", "Powered"]).is_empty());
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "assigned-numbers")]
+
+use bluebus::{
+    appearance_name, characteristic_name, company_name, descriptor_name, major_class_name,
+    minor_class_name, service_name, BtUuid,
+};
+
+#[test]
+fn uuid_names() {
+    let uuid = BtUuid::from_u16;
+    assert_eq!(service_name(&uuid(0x180d)), Some("Heart Rate"));
+    assert_eq!(service_name(&uuid(0x1101)), Some("Serial Port"));
+    assert_eq!(service_name(&uuid(0xfeaa)), Some("Google LLC"));
+    assert_eq!(service_name(&uuid(0x2a37)), None);
+    assert_eq!(service_name(&BtUuid::from_u128(0x1234)), None);
+
+    assert_eq!(
+        characteristic_name(&uuid(0x2a37)),
+        Some("Heart Rate Measurement")
+    );
+    assert_eq!(characteristic_name(&uuid(0x180d)), None);
+
+    assert_eq!(
+        descriptor_name(&uuid(0x2902)),
+        Some("Client Characteristic Configuration")
+    );
+    assert_eq!(descriptor_name(&uuid(0x2a37)), None);
+
+    assert_eq!(uuid(0x180d).name(), Some("Heart Rate"));
+    assert_eq!(uuid(0x2a37).name(), Some("Heart Rate Measurement"));
+    assert_eq!(
+        uuid(0x2902).name(),
+        Some("Client Characteristic Configuration")
+    );
+    assert_eq!(BtUuid::from_u32(0x1234_5678).name(), None);
+}
+
+#[test]
+fn company_names() {
+    assert_eq!(company_name(0x004c), Some("Apple, Inc."));
+    assert_eq!(company_name(0x0006), Some("Microsoft"));
+    assert_eq!(company_name(0x0499), Some("Ruuvi Innovations Ltd."));
+    assert_eq!(company_name(0xffff), None);
+}
+
+#[test]
+fn appearance_names() {
+    assert_eq!(appearance_name(0x0000), Some("Unknown"));
+    assert_eq!(appearance_name(0x03c1), Some("Keyboard"));
+    assert_eq!(appearance_name(0x0943), Some("Headphones"));
+    assert_eq!(appearance_name(0x03c0), Some("Human Interface Device"));
+    assert_eq!(appearance_name(0x03ff), Some("Human Interface Device"));
+    assert_eq!(appearance_name(0x0bc0), None);
+}
+
+#[test]
+fn class_names() {
+    assert_eq!(major_class_name(0x02), Some("Phone"));
+    assert_eq!(major_class_name(0x1f), Some("Uncategorized"));
+    assert_eq!(major_class_name(0x0a), None);
+
+    assert_eq!(minor_class_name(0x01, 0x03), Some("Laptop"));
+    assert_eq!(minor_class_name(0x03, 0x00), Some("Fully available"));
+    assert_eq!(minor_class_name(0x03, 0x08), Some("1% to 17% utilized"));
+    assert_eq!(minor_class_name(0x05, 0x10), Some("Keyboard"));
+    assert_eq!(
+        minor_class_name(0x05, 0x30),
+        Some("Combo keyboard/pointing device")
+    );
+    assert_eq!(minor_class_name(0x05, 0x22), Some("Gamepad"));
+    assert_eq!(minor_class_name(0x06, 0x30), Some("Printer"));
+    assert_eq!(minor_class_name(0x06, 0x04), Some("Display"));
+    assert_eq!(minor_class_name(0x06, 0x00), None);
+    assert_eq!(minor_class_name(0x07, 0x00), None);
+}
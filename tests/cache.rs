@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use bluebus::{DeviceField, DeviceInfo};
+use zbus::zvariant::{OwnedValue, Value};
+
+const ADDRESS: &str = "00:11:22:33:44:55";
+
+fn device() -> DeviceInfo {
+    let properties: HashMap<String, OwnedValue> = [
+        ("Address", Value::from(ADDRESS)),
+        ("Alias", Value::from("Keyboard")),
+        ("Name", Value::from("K380")),
+        ("Paired", Value::from(true)),
+        ("RSSI", Value::from(-60i16)),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_owned(), value.try_into().unwrap()))
+    .collect();
+
+    DeviceInfo::try_from(&properties).unwrap()
+}
+
+fn manufacturer_data(data: &[u8]) -> Value<'static> {
+    Value::from(HashMap::from([(0x004cu16, Value::from(data.to_vec()))]))
+}
+
+#[test]
+fn apply_changes() {
+    let mut device = device();
+    let changed = HashMap::from([
+        ("Alias", Value::from("Desk keyboard")),
+        ("Connected", Value::from(true)),
+        ("RSSI", Value::from(-42i16)),
+        ("ManufacturerData", manufacturer_data(&[0x02, 0x15])),
+        ("Modalias", Value::from("usb:v046DpB342d0402")),
+    ]);
+
+    let mut fields = device.apply_changes(&changed);
+    fields.sort_by_key(|field| field.property_name());
+    assert_eq!(
+        fields,
+        [
+            DeviceField::Alias,
+            DeviceField::Connected,
+            DeviceField::ManufacturerData,
+            DeviceField::Rssi
+        ]
+    );
+    assert_eq!(device.alias, "Desk keyboard");
+    assert!(device.connected);
+    assert_eq!(device.rssi, Some(-42));
+    assert_eq!(device.manufacturer_data[&0x004c], [0x02, 0x15]);
+}
+
+#[test]
+fn unchanged_values_are_not_reported() {
+    let mut device = device();
+    let changed = HashMap::from([
+        ("Alias", Value::from("Keyboard")),
+        ("Paired", Value::from(true)),
+        ("RSSI", Value::from(-60i16)),
+    ]);
+    assert!(device.apply_changes(&changed).is_empty());
+
+    let changed = HashMap::from([("ManufacturerData", manufacturer_data(&[0x02]))]);
+    assert_eq!(
+        device.apply_changes(&changed),
+        [DeviceField::ManufacturerData]
+    );
+    assert!(device.apply_changes(&changed).is_empty());
+}
+
+#[test]
+fn wrongly_typed_values_are_ignored() {
+    let mut device = device();
+    let changed = HashMap::from([
+        ("Address", Value::from(0u32)),
+        ("Alias", Value::from(true)),
+        ("Paired", Value::from("no")),
+        ("Name", Value::from(1u8)),
+        ("RSSI", Value::from(-60i32)),
+        ("UUIDs", Value::from("0000180f-0000-1000-8000-00805f9b34fb")),
+    ]);
+
+    assert!(device.apply_changes(&changed).is_empty());
+    assert_eq!(device.address, ADDRESS);
+    assert_eq!(device.alias, "Keyboard");
+    assert!(device.paired);
+    assert_eq!(device.name.as_deref(), Some("K380"));
+    assert_eq!(device.rssi, Some(-60));
+    assert!(device.uuids.is_empty());
+}
+
+#[test]
+fn invalidate() {
+    let mut device = device();
+
+    let mut fields = device.invalidate(&["Address", "Alias", "Name", "Paired", "Icon", "Modalias"]);
+    fields.sort_by_key(|field| field.property_name());
+    assert_eq!(
+        fields,
+        [DeviceField::Alias, DeviceField::Name, DeviceField::Paired]
+    );
+    assert_eq!(device.address, ADDRESS);
+    assert_eq!(device.alias, ADDRESS);
+    assert_eq!(device.name, None);
+    assert!(!device.paired);
+
+    assert!(device.invalidate(&["Alias", "Name", "Paired"]).is_empty());
+}
@@ -0,0 +1,139 @@
+use bluebus::{
+    AudioVideoClass, ClassOfDevice, ComputerClass, HealthClass, MajorClass, MinorClass,
+    PeripheralClass, PhoneClass, ServiceClass, WearableClass,
+};
+use zbus::zvariant::{OwnedValue, Value};
+
+#[test]
+fn decode() {
+    use ServiceClass::*;
+
+    let cases = [
+        (
+            0x5a020c,
+            MajorClass::Phone,
+            MinorClass::Phone(PhoneClass::Smartphone),
+            vec![Networking, Capturing, ObjectTransfer, Telephony],
+        ),
+        (
+            0x240404,
+            MajorClass::AudioVideo,
+            MinorClass::AudioVideo(AudioVideoClass::Headset),
+            vec![Rendering, Audio],
+        ),
+        (
+            0x00010c,
+            MajorClass::Computer,
+            MinorClass::Computer(ComputerClass::Laptop),
+            vec![],
+        ),
+        (
+            0x00013c,
+            MajorClass::Computer,
+            MinorClass::Computer(ComputerClass::Reserved(0x0f)),
+            vec![],
+        ),
+        (
+            0x002540,
+            MajorClass::Peripheral,
+            MinorClass::Peripheral {
+                keyboard: true,
+                pointing: false,
+                kind: PeripheralClass::Uncategorized,
+            },
+            vec![LimitedDiscoverableMode],
+        ),
+        (
+            0x000588,
+            MajorClass::Peripheral,
+            MinorClass::Peripheral {
+                keyboard: false,
+                pointing: true,
+                kind: PeripheralClass::Gamepad,
+            },
+            vec![],
+        ),
+        (
+            0x0006a0,
+            MajorClass::Imaging,
+            MinorClass::Imaging {
+                display: false,
+                camera: true,
+                scanner: false,
+                printer: true,
+            },
+            vec![],
+        ),
+        (
+            0x000360,
+            MajorClass::NetworkAccessPoint,
+            MinorClass::NetworkAccessPoint(3),
+            vec![],
+        ),
+        (
+            0x004704,
+            MajorClass::Wearable,
+            MinorClass::Wearable(WearableClass::Wristwatch),
+            vec![LeAudio],
+        ),
+        (
+            0x000908,
+            MajorClass::Health,
+            MinorClass::Health(HealthClass::Thermometer),
+            vec![],
+        ),
+        (
+            0x000000,
+            MajorClass::Miscellaneous,
+            MinorClass::Other(0),
+            vec![],
+        ),
+        (
+            0x801f04,
+            MajorClass::Uncategorized,
+            MinorClass::Other(1),
+            vec![Information],
+        ),
+        (
+            0x000a00,
+            MajorClass::Reserved(0x0a),
+            MinorClass::Other(0),
+            vec![],
+        ),
+    ];
+
+    for (raw, major, minor, services) in cases {
+        let class = ClassOfDevice::from_u32(raw);
+        assert_eq!(class.major(), major, "{:?}", class);
+        assert_eq!(class.minor(), minor, "{:?}", class);
+        assert_eq!(class.service_classes(), services, "{:?}", class);
+        for service in ServiceClass::ALL {
+            assert_eq!(class.has_service(service), services.contains(&service));
+        }
+    }
+}
+
+#[test]
+fn raw_value() {
+    let class = ClassOfDevice::from_u32(0xff5a_020c);
+    assert_eq!(class.as_u32(), 0x5a020c);
+    assert_eq!(class.major_bits(), 0x02);
+    assert_eq!(class.minor_bits(), 0x03);
+    assert_eq!(u32::from(class), 0x5a020c);
+    assert_eq!(ClassOfDevice::from(0x5a020c), class);
+    assert_eq!(format!("{:?}", class), "ClassOfDevice(0x5a020c)");
+}
+
+#[test]
+fn zvariant_round_trip() {
+    let class = ClassOfDevice::from_u32(0x240404);
+
+    let value = Value::from(class);
+    assert_eq!(value, Value::U32(0x240404));
+    assert_eq!(ClassOfDevice::try_from(value).unwrap(), class);
+
+    let value = OwnedValue::from(0x240404u32);
+    assert_eq!(ClassOfDevice::try_from(value).unwrap(), class);
+
+    assert!(ClassOfDevice::try_from(Value::from("0x240404")).is_err());
+}
@@ -0,0 +1,118 @@
+use bluebus::{DistanceEstimator, KalmanFilter, MovingAverage, ProximityZone, RssiFilter};
+
+fn close(value: Option<f64>, expected: f64) -> bool {
+    value.is_some_and(|value| (value - expected).abs() < 1e-9)
+}
+
+#[test]
+fn moving_average() {
+    let mut filter = MovingAverage::new(3);
+    assert_eq!(filter.value(), None);
+
+    assert_eq!(filter.update(-60), -60.0);
+    assert_eq!(filter.update(-70), -65.0);
+    assert_eq!(filter.update(-80), -70.0);
+    assert_eq!(filter.update(-90), -80.0);
+    assert!(close(filter.value(), -80.0));
+
+    filter.reset();
+    assert_eq!(filter.value(), None);
+    assert_eq!(filter.update(-50), -50.0);
+
+    let mut filter = MovingAverage::new(0);
+    filter.update(-60);
+    assert_eq!(filter.update(-70), -70.0);
+}
+
+#[test]
+fn kalman_filter() {
+    let mut filter = KalmanFilter::default();
+    assert_eq!(filter.value(), None);
+    assert_eq!(filter.update(-60), -60.0);
+
+    let estimate = filter.update(-80);
+    assert!(estimate < -60.0 && estimate > -80.0);
+
+    for _ in 0..200 {
+        filter.update(-80);
+    }
+    assert!(filter
+        .value()
+        .is_some_and(|value| (value + 80.0).abs() < 0.5));
+
+    filter.reset();
+    assert_eq!(filter.value(), None);
+    assert_eq!(filter.update(-70), -70.0);
+}
+
+#[test]
+fn distance() {
+    let estimator = DistanceEstimator::new();
+    assert!(close(estimator.distance(-41.0, Some(0)), 1.0));
+    assert!(close(estimator.distance(-61.0, Some(0)), 10.0));
+    assert_eq!(estimator.distance(-61.0, None), None);
+
+    let estimator = DistanceEstimator::new()
+        .with_measured_power(-59)
+        .with_path_loss_exponent(4.0);
+    assert!(close(estimator.distance(-59.0, Some(0)), 1.0));
+    assert!(close(estimator.distance(-99.0, None), 10.0));
+}
+
+#[test]
+fn zones() {
+    let estimator = DistanceEstimator::new();
+    assert_eq!(estimator.zone(0.2), ProximityZone::Immediate);
+    assert_eq!(estimator.zone(1.0), ProximityZone::Near);
+    assert_eq!(estimator.zone(3.0), ProximityZone::Far);
+
+    let estimator = DistanceEstimator::new().with_zones(3.0, 0.5);
+    assert_eq!(estimator.zone(0.2), ProximityZone::Immediate);
+    assert_eq!(estimator.zone(1.0), ProximityZone::Near);
+    assert_eq!(estimator.zone(4.0), ProximityZone::Far);
+}
+
+#[test]
+fn zone_from() {
+    let estimator = DistanceEstimator::new();
+    assert_eq!(
+        estimator.zone_from(ProximityZone::Near, 3.2),
+        ProximityZone::Near
+    );
+    assert_eq!(
+        estimator.zone_from(ProximityZone::Near, 3.7),
+        ProximityZone::Far
+    );
+    assert_eq!(
+        estimator.zone_from(ProximityZone::Far, 2.8),
+        ProximityZone::Far
+    );
+    assert_eq!(
+        estimator.zone_from(ProximityZone::Far, 2.3),
+        ProximityZone::Near
+    );
+    assert_eq!(
+        estimator.zone_from(ProximityZone::Far, 0.1),
+        ProximityZone::Immediate
+    );
+    assert_eq!(
+        estimator.zone_from(ProximityZone::Near, 1.0),
+        ProximityZone::Near
+    );
+
+    let estimator = DistanceEstimator::new().with_hysteresis(1.5);
+    assert_eq!(
+        estimator.zone_from(ProximityZone::Far, 0.1),
+        ProximityZone::Near
+    );
+    assert_eq!(
+        estimator.zone_from(ProximityZone::Near, 100.0),
+        ProximityZone::Far
+    );
+
+    let estimator = DistanceEstimator::new().with_hysteresis(f64::NAN);
+    assert_eq!(
+        estimator.zone_from(ProximityZone::Near, 3.2),
+        ProximityZone::Far
+    );
+}
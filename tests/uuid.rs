@@ -0,0 +1,123 @@
+use bluebus::{BtUuid, BLUETOOTH_BASE_UUID};
+use serde::de::IntoDeserializer;
+use serde::Deserialize;
+use zbus::zvariant::{serialized::Context, to_bytes, OwnedValue, Value, LE};
+
+const HEART_RATE: &str = "0000180d-0000-1000-8000-00805f9b34fb";
+
+#[test]
+fn short_forms() {
+    let uuid = BtUuid::from_u16(0x180d);
+    assert_eq!(uuid, BtUuid::from_u32(0x180d));
+    assert_eq!(uuid.as_u128(), BLUETOOTH_BASE_UUID | (0x180d << 96));
+    assert_eq!(uuid.to_full_string(), HEART_RATE);
+    assert_eq!(uuid.as_u16(), Some(0x180d));
+    assert_eq!(uuid.as_u32(), Some(0x180d));
+
+    let uuid = BtUuid::from_u32(0x1234_5678);
+    assert_eq!(
+        uuid.to_full_string(),
+        "12345678-0000-1000-8000-00805f9b34fb"
+    );
+    assert_eq!(uuid.as_u16(), None);
+    assert_eq!(uuid.as_u32(), Some(0x1234_5678));
+
+    let uuid = BtUuid::from_u128(0xf782_6da6_4fa2_4e98_8024_bc5b_71e0_893e);
+    assert_eq!(uuid.as_u16(), None);
+    assert_eq!(uuid.as_u32(), None);
+    assert_eq!(BtUuid::from_u128(BLUETOOTH_BASE_UUID).as_u16(), Some(0));
+}
+
+#[test]
+fn parse() {
+    let heart_rate = BtUuid::from_u16(0x180d);
+    for input in [
+        "180d",
+        "180D",
+        "0x180d",
+        "0X180D",
+        "0000180d",
+        HEART_RATE,
+        "0000180D-0000-1000-8000-00805F9B34FB",
+        "0000180d00001000800000805f9b34fb",
+    ] {
+        assert_eq!(input.parse::<BtUuid>().unwrap(), heart_rate, "{}", input);
+    }
+
+    assert_eq!(
+        "F7826DA6-4FA2-4E98-8024-BC5B71E0893E"
+            .parse::<BtUuid>()
+            .unwrap(),
+        BtUuid::from_u128(0xf782_6da6_4fa2_4e98_8024_bc5b_71e0_893e)
+    );
+}
+
+#[test]
+fn parse_invalid() {
+    for input in [
+        "",
+        "0x",
+        "18d",
+        "180d0",
+        "18 0d",
+        "180g",
+        "+180",
+        "0000180d-0000-1000-8000_00805f9b34fb",
+        "0000180d-0000-1000-8000-00805f9b34f",
+        "0000180d-0000-1000-8000-00805f9b34fbb",
+        "0000180d00001000800000805f9b34fg",
+    ] {
+        assert!(input.parse::<BtUuid>().is_err(), "{:?}", input);
+    }
+}
+
+#[test]
+fn display_round_trip() {
+    for (uuid, shown) in [
+        (BtUuid::from_u16(0x180d), "180d"),
+        (BtUuid::from_u16(0x0001), "0001"),
+        (BtUuid::from_u32(0x1234_5678), "12345678"),
+        (
+            BtUuid::from_u128(0xf782_6da6_4fa2_4e98_8024_bc5b_71e0_893e),
+            "f7826da6-4fa2-4e98-8024-bc5b71e0893e",
+        ),
+    ] {
+        assert_eq!(uuid.to_string(), shown);
+        assert_eq!(shown.parse::<BtUuid>().unwrap(), uuid);
+        assert_eq!(uuid.to_full_string().parse::<BtUuid>().unwrap(), uuid);
+    }
+}
+
+#[test]
+fn zvariant_round_trip() {
+    let uuid = BtUuid::from_u16(0x180d);
+
+    let value = Value::from(uuid);
+    assert_eq!(value, Value::from(HEART_RATE));
+    assert_eq!(BtUuid::try_from(value).unwrap(), uuid);
+
+    let value = OwnedValue::try_from(Value::from(uuid)).unwrap();
+    assert_eq!(BtUuid::try_from(value).unwrap(), uuid);
+
+    assert!(BtUuid::try_from(Value::from("heart rate")).is_err());
+    assert!(BtUuid::try_from(Value::from(0x180du32)).is_err());
+
+    let ctxt = Context::new_dbus(LE, 0);
+    let data = to_bytes(ctxt, &uuid).unwrap();
+    let (decoded, _): (BtUuid, _) = data.deserialize().unwrap();
+    assert_eq!(decoded, uuid);
+
+    let (decoded, _): (String, _) = data.deserialize().unwrap();
+    assert_eq!(decoded, HEART_RATE);
+}
+
+#[test]
+fn serde_round_trip() {
+    let deserialize = |input: &str| {
+        BtUuid::deserialize(IntoDeserializer::<serde::de::value::Error>::into_deserializer(input))
+    };
+
+    assert_eq!(deserialize("180d").unwrap(), BtUuid::from_u16(0x180d));
+    assert_eq!(deserialize(HEART_RATE).unwrap(), BtUuid::from_u16(0x180d));
+    assert!(deserialize("not a uuid").is_err());
+}